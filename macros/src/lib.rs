@@ -0,0 +1,148 @@
+// Copyright 2022-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Compile-time validated hotkeys for the [`global_hotkey`] crate.
+//!
+//! [`hotkey!`] parses a hotkey literal while compiling, so a typo in a default binding
+//! is a compile error rather than a runtime one:
+//!
+//! ```
+//! use global_hotkey::hotkey::{Code, HotKey, Modifiers};
+//! use global_hotkey_macros::hotkey;
+//!
+//! const SEARCH: HotKey = hotkey!("ctrl+shift+k");
+//! const SAVE: HotKey = hotkey!("Ctrl+KeyS");
+//! assert_eq!(SEARCH, "Ctrl+Shift+K".parse().unwrap());
+//! assert_eq!(SAVE, HotKey::new(Some(Modifiers::CONTROL), Code::KeyS));
+//! ```
+//!
+//! ```compile_fail
+//! # use global_hotkey_macros::hotkey;
+//! // error: Couldn't recognize "Shft" as a modifier in hotkey "Ctrl+Shft+K", did you mean "Shift"?
+//! let hotkey = hotkey!("Ctrl+Shft+K");
+//! ```
+
+use global_hotkey::hotkey::{HotKey, Modifiers, MouseButton, Trigger, WheelDirection};
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, LitStr};
+
+/// Parses a hotkey literal at compile time, with the syntax of `HotKey`'s `FromStr` implementation,
+/// and expands to a const [`HotKey`].
+///
+/// The expansion can be used in const contexts, like `const SAVE: HotKey = hotkey!("Ctrl+S");`.
+/// A hotkey that doesn't parse is a compile error pointing at the literal.
+///
+/// `CmdOrCtrl` expands to [`CMD_OR_CTRL`](global_hotkey::hotkey::CMD_OR_CTRL) of the platform
+/// the hotkey is compiled for, not of the one running the compiler.
+///
+/// ```
+/// # use global_hotkey::hotkey::{HotKey, MouseButton, Trigger};
+/// # use global_hotkey_macros::hotkey;
+/// assert_eq!(hotkey!("CmdOrCtrl+Shift+K"), "CmdOrCtrl+Shift+K".parse::<HotKey>().unwrap());
+/// assert_eq!(hotkey!("RCtrl+Mouse4").trigger(), Trigger::Mouse(MouseButton::Back));
+/// assert_eq!(hotkey!("CmdOrCtrl"), "CmdOrCtrl".parse::<HotKey>().unwrap());
+/// assert_eq!(hotkey!("Ctrl++"), "Ctrl+Plus".parse::<HotKey>().unwrap());
+/// ```
+#[proc_macro]
+pub fn hotkey(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let literal = parse_macro_input!(input as LitStr);
+    let value = literal.value();
+    let expanded = match value.parse::<HotKey>() {
+        Ok(_) if value.split('+').any(is_cmd_or_ctrl) => {
+            // parse the other tokens alone, `CmdOrCtrl` is added for the target platform
+            let rest = value.split('+').filter(|token| !is_cmd_or_ctrl(token));
+            let rest = rest.collect::<Vec<_>>().join("+");
+            match rest.trim() {
+                "" => expand(&HotKey::modifiers_only(Modifiers::empty()), true),
+                rest => match rest.parse::<HotKey>() {
+                    Ok(rest) => expand(&rest, true),
+                    Err(error) => {
+                        let message = format!("{error}, in {rest:?} without `CmdOrCtrl`");
+                        syn::Error::new(literal.span(), message).to_compile_error()
+                    }
+                },
+            }
+        }
+        Ok(hotkey) => expand(&hotkey, false),
+        Err(error) => {
+            let span = token_span(&literal, error.span()).unwrap_or_else(|| literal.span());
+            syn::Error::new(span, error).to_compile_error()
+        }
+    };
+    expanded.into()
+}
+
+/// Returns the span of `range` in the literal, which is only available on nightly compilers.
+fn token_span(literal: &LitStr, range: std::ops::Range<usize>) -> Option<Span> {
+    let token = literal.token();
+    // the offsets of the value only match the source when the literal has no escapes
+    if token.to_string() != format!("\"{}\"", literal.value()) {
+        return None;
+    }
+    token.subspan(range.start + 1..range.end + 1)
+}
+
+fn is_cmd_or_ctrl(token: &str) -> bool {
+    matches!(
+        token.trim().to_uppercase().as_str(),
+        "COMMANDORCONTROL" | "COMMANDORCTRL" | "CMDORCTRL" | "CMDORCONTROL"
+    )
+}
+
+fn expand(hotkey: &HotKey, cmd_or_ctrl: bool) -> TokenStream {
+    let bits = hotkey.mods().bits();
+    let mut mods = quote!(::global_hotkey::hotkey::Modifiers::from_bits_retain(#bits));
+    if cmd_or_ctrl {
+        mods = quote!(#mods.union(::global_hotkey::hotkey::CMD_OR_CTRL));
+    }
+    let sides = hotkey.sides().bits();
+    let trigger = match hotkey.trigger() {
+        Trigger::ModifiersOnly => quote!(ModifiersOnly),
+        Trigger::Key(code) => {
+            let code = format_ident!("{}", code.to_string());
+            quote!(Key(::global_hotkey::hotkey::Code::#code))
+        }
+        Trigger::Character(c) => quote!(Character(#c)),
+        Trigger::Mouse(button) => {
+            let button = match button {
+                MouseButton::Left => quote!(Left),
+                MouseButton::Right => quote!(Right),
+                MouseButton::Middle => quote!(Middle),
+                MouseButton::Back => quote!(Back),
+                MouseButton::Forward => quote!(Forward),
+                button => {
+                    let number = button.number();
+                    quote!(Other(#number))
+                }
+            };
+            quote!(Mouse(::global_hotkey::hotkey::MouseButton::#button))
+        }
+        Trigger::Wheel(direction) => {
+            let direction = match direction {
+                WheelDirection::Up => quote!(Up),
+                WheelDirection::Down => quote!(Down),
+                WheelDirection::Left => quote!(Left),
+                WheelDirection::Right => quote!(Right),
+            };
+            quote!(Wheel(::global_hotkey::hotkey::WheelDirection::#direction))
+        }
+        trigger => {
+            let message = format!("`hotkey!` doesn't support the trigger {trigger:?}");
+            return syn::Error::new(Span::call_site(), message).to_compile_error();
+        }
+    };
+
+    quote! {
+        {
+            const HOTKEY: ::global_hotkey::hotkey::HotKey =
+                ::global_hotkey::hotkey::HotKey::from_parsed(
+                    #mods,
+                    ::global_hotkey::hotkey::ModifierSides::from_bits_retain(#sides),
+                    ::global_hotkey::hotkey::Trigger::#trigger,
+                );
+            HOTKEY
+        }
+    }
+}
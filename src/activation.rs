@@ -0,0 +1,735 @@
+// Copyright 2022-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Registration options that change when the events of a [`HotKey`] are emitted.
+//!
+//! The platform implementations only report the raw presses and releases of the registered hotkeys,
+//! these are turned into the events of each hotkey's [`Activation`] by [`Activations::send`].
+//!
+//! Only [`Activations::send`], [`Activations::park`] and the hold timer read the clock.
+//! The trackers and `dispatch` take the current time as a `now` argument instead,
+//! which is how the tests drive them through taps, holds and timeouts without sleeping.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Condvar, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+#[cfg(doc)]
+use crate::{hotkey::HotKey, GlobalHotKeyManager};
+use crate::{GlobalHotKeyEvent, HotKeyState};
+
+/// Options of a [`HotKey`] registered with [`GlobalHotKeyManager::register_with_options`].
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::time::Duration;
+/// # use global_hotkey::{Activation, GlobalHotKeyManager, HotKeyOptions, hotkey::{HotKey, Modifiers}};
+/// let manager = GlobalHotKeyManager::new().unwrap();
+/// // double-tap Shift
+/// let hotkey = HotKey::modifiers_only(Modifiers::SHIFT);
+/// let options = HotKeyOptions::new().with_activation(Activation::Taps {
+///     count: 2,
+///     window: Duration::from_millis(300),
+/// });
+/// manager.register_with_options(hotkey, options).unwrap();
+/// ```
+///
+/// ```no_run
+/// # use std::time::Duration;
+/// # use global_hotkey::{Activation, GlobalHotKeyManager, HotKeyOptions, hotkey::{HotKey, Modifiers, Code}};
+/// let manager = GlobalHotKeyManager::new().unwrap();
+/// // hold Ctrl+Q for a second to quit, with progress events to fill an indicator
+/// let hotkey = HotKey::new(Some(Modifiers::CONTROL), Code::KeyQ);
+/// let options = HotKeyOptions::new().with_activation(Activation::Hold {
+///     duration: Duration::from_secs(1),
+///     progress: Some(Duration::from_millis(100)),
+/// });
+/// manager.register_with_options(hotkey, options).unwrap();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct HotKeyOptions {
+    activation: Activation,
+    repeat: bool,
+}
+
+impl HotKeyOptions {
+    /// Creates the default options, which behave like [`GlobalHotKeyManager::register`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets what has to be done with the hotkey for its events to be emitted.
+    pub fn with_activation(mut self, activation: Activation) -> Self {
+        self.activation = activation;
+        self
+    }
+
+    /// Returns what has to be done with the hotkey for its events to be emitted.
+    pub fn activation(&self) -> Activation {
+        self.activation
+    }
+
+    /// Sets whether the key repeats sent by the OS while the hotkey is held
+    /// are emitted as [`HotKeyState::Repeat`], which is off by default.
+    ///
+    /// Repeats are only emitted after the hotkey's [`HotKeyState::Pressed`] event.
+    ///
+    /// ## Platform-specific:
+    ///
+    /// - **macOS**: Unsupported, the OS doesn't repeat hotkeys.
+    pub fn with_repeat(mut self, repeat: bool) -> Self {
+        self.repeat = repeat;
+        self
+    }
+
+    /// Returns whether key repeats are emitted as [`HotKeyState::Repeat`].
+    pub fn repeat(&self) -> bool {
+        self.repeat
+    }
+}
+
+/// What has to be done with a [`HotKey`] for its events to be emitted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum Activation {
+    /// Emit [`HotKeyState::Pressed`] and [`HotKeyState::Released`] as soon as the hotkey
+    /// is pressed and released.
+    #[default]
+    Press,
+    /// Only emit the events of the `count`th consecutive press of the hotkey, each press
+    /// following the previous one within `window`.
+    ///
+    /// The events report the number of taps in [`GlobalHotKeyEvent::tap_count`].
+    /// A `count` of `0` or `1` is the same as [`Activation::Press`].
+    Taps { count: u32, window: Duration },
+    /// Only emit [`HotKeyState::Pressed`] once the hotkey has been held for `duration`.
+    ///
+    /// While waiting, [`HotKeyState::Holding`] is emitted every `progress` interval if set.
+    /// Releasing the hotkey before `duration` emits [`HotKeyState::Cancelled`] instead of
+    /// [`HotKeyState::Released`].
+    Hold {
+        duration: Duration,
+        progress: Option<Duration>,
+    },
+}
+
+/// The activation state of a registered hotkey.
+#[derive(Debug)]
+enum Tracker {
+    Press,
+    Taps(TapTracker),
+    Hold(HoldTracker),
+}
+
+impl Tracker {
+    fn new(activation: Activation) -> Self {
+        match activation {
+            Activation::Taps { count, window } if count > 1 => {
+                Self::Taps(TapTracker::new(count, window))
+            }
+            Activation::Hold { duration, progress } => {
+                Self::Hold(HoldTracker::new(duration, progress))
+            }
+            _ => Self::Press,
+        }
+    }
+
+    /// Returns `true` if the hotkey is held and its [`HotKeyState::Pressed`] event was emitted.
+    fn is_active(&self) -> bool {
+        match self {
+            Self::Press => true,
+            Self::Taps(taps) => taps.fired,
+            Self::Hold(hold) => hold.fired,
+        }
+    }
+}
+
+/// Counts the taps of a hotkey registered with [`Activation::Taps`].
+#[derive(Debug)]
+pub(crate) struct TapTracker {
+    count: u32,
+    window: Duration,
+    taps: u32,
+    last_press: Option<Instant>,
+    /// Set from the press completing the taps until it is released.
+    fired: bool,
+}
+
+impl TapTracker {
+    pub fn new(count: u32, window: Duration) -> Self {
+        Self {
+            count,
+            window,
+            taps: 0,
+            last_press: None,
+            fired: false,
+        }
+    }
+
+    /// Registers a press, returns the tap count once it reaches the expected count.
+    pub fn press(&mut self, now: Instant) -> Option<u32> {
+        let in_window = self
+            .last_press
+            .is_some_and(|last| now.saturating_duration_since(last) <= self.window);
+        self.taps = if in_window { self.taps + 1 } else { 1 };
+        self.last_press = Some(now);
+
+        if self.taps == self.count {
+            self.taps = 0;
+            self.last_press = None;
+            self.fired = true;
+            Some(self.count)
+        } else {
+            None
+        }
+    }
+
+    /// Registers a release, returns the tap count if it ends the press that completed the taps.
+    pub fn release(&mut self) -> Option<u32> {
+        std::mem::take(&mut self.fired).then_some(self.count)
+    }
+}
+
+/// Times how long a hotkey registered with [`Activation::Hold`] is held.
+#[derive(Debug)]
+pub(crate) struct HoldTracker {
+    duration: Duration,
+    progress: Option<Duration>,
+    pressed_at: Option<Instant>,
+    next_progress: Option<Instant>,
+    /// Set once the hotkey has been held for `duration`.
+    fired: bool,
+}
+
+impl HoldTracker {
+    pub fn new(duration: Duration, progress: Option<Duration>) -> Self {
+        Self {
+            duration,
+            progress: progress.filter(|p| !p.is_zero()),
+            pressed_at: None,
+            next_progress: None,
+            fired: false,
+        }
+    }
+
+    /// Registers a press, returns `true` if it started holding the hotkey.
+    ///
+    /// Returns `false` for the repeated presses of a hotkey that is already held.
+    pub fn press(&mut self, now: Instant) -> bool {
+        if self.pressed_at.is_some() {
+            return false;
+        }
+        self.pressed_at = Some(now);
+        self.next_progress = self.progress.map(|p| now + p);
+        self.fired = false;
+        true
+    }
+
+    /// Registers a release, returns [`HotKeyState::Released`] if the hotkey was held long enough
+    /// and [`HotKeyState::Cancelled`] otherwise.
+    pub fn release(&mut self) -> Option<HotKeyState> {
+        self.pressed_at.take()?;
+        self.next_progress = None;
+        if std::mem::take(&mut self.fired) {
+            Some(HotKeyState::Released)
+        } else {
+            Some(HotKeyState::Cancelled)
+        }
+    }
+
+    /// Returns when [`HoldTracker::tick`] should be called next, if the hotkey is held.
+    pub fn next_tick(&self) -> Option<Instant> {
+        if self.fired {
+            return None;
+        }
+        let deadline = self.pressed_at? + self.duration;
+        Some(self.next_progress.map_or(deadline, |p| p.min(deadline)))
+    }
+
+    /// Returns the state to emit for the current press at `now`, if any.
+    pub fn tick(&mut self, now: Instant) -> Option<HotKeyState> {
+        if self.fired {
+            return None;
+        }
+        let held = now.saturating_duration_since(self.pressed_at?);
+
+        if held >= self.duration {
+            self.fired = true;
+            self.next_progress = None;
+            Some(HotKeyState::Pressed)
+        } else if self.next_progress.is_some_and(|p| p <= now) {
+            self.next_progress = self.progress.map(|p| now + p);
+            Some(HotKeyState::Holding(held))
+        } else {
+            None
+        }
+    }
+}
+
+/// The options and activation state of a registered hotkey.
+#[derive(Debug)]
+pub(crate) struct Entry {
+    options: HotKeyOptions,
+    tracker: Tracker,
+    repeat: bool,
+    repeats: u32,
+}
+
+impl Entry {
+    pub fn new(options: HotKeyOptions) -> Self {
+        Self {
+            options,
+            tracker: Tracker::new(options.activation),
+            repeat: options.repeat,
+            repeats: 0,
+        }
+    }
+
+    /// Returns `true` if the events of the hotkey are emitted as they are reported.
+    fn is_default(&self) -> bool {
+        matches!(self.tracker, Tracker::Press) && !self.repeat
+    }
+
+    /// Turns a raw event into the event to emit, if any.
+    ///
+    /// Also returns `true` if the event started a hold, which the hold timer has to tick.
+    pub fn handle(
+        &mut self,
+        event: GlobalHotKeyEvent,
+        now: Instant,
+    ) -> (Option<GlobalHotKeyEvent>, bool) {
+        if let HotKeyState::Repeat(_) = event.state {
+            if !self.repeat || !self.tracker.is_active() {
+                return (None, false);
+            }
+            self.repeats += 1;
+            let tap_count = match &self.tracker {
+                Tracker::Taps(taps) => taps.count,
+                _ => 1,
+            };
+            let event = GlobalHotKeyEvent {
+                state: HotKeyState::Repeat(self.repeats),
+                tap_count,
+                ..event
+            };
+            return (Some(event), false);
+        }
+
+        if event.state == HotKeyState::Pressed {
+            self.repeats = 0;
+        }
+
+        match &mut self.tracker {
+            Tracker::Press => (Some(event), false),
+            Tracker::Taps(taps) => {
+                let tap_count = match event.state {
+                    HotKeyState::Pressed => taps.press(now),
+                    HotKeyState::Released => taps.release(),
+                    _ => Some(event.tap_count),
+                };
+                let event = tap_count.map(|tap_count| GlobalHotKeyEvent { tap_count, ..event });
+                (event, false)
+            }
+            Tracker::Hold(hold) => match event.state {
+                HotKeyState::Pressed => (None, hold.press(now)),
+                HotKeyState::Released => {
+                    let event = hold
+                        .release()
+                        .map(|state| GlobalHotKeyEvent { state, ..event });
+                    (event, false)
+                }
+                _ => (Some(event), false),
+            },
+        }
+    }
+}
+
+/// The options and activation state of the hotkeys registered with one [`GlobalHotKeyManager`].
+#[derive(Debug, Default)]
+pub(crate) struct Activations {
+    entries: Mutex<BTreeMap<u32, Entry>>,
+    /// The hotkeys and sequences pressed and not released yet, as reported by the platform.
+    held: Mutex<BTreeSet<u32>>,
+    /// Wakes the hold timer up when a hold starts or the options are cleared.
+    timer: Condvar,
+    /// Set while the hold timer thread runs, only changed with `entries` locked.
+    timer_running: AtomicBool,
+}
+
+impl Activations {
+    /// Sets the options of the hotkey with this id, replacing its previous ones.
+    pub fn set_options(&self, id: u32, options: HotKeyOptions) {
+        let mut entries = self.entries.lock().unwrap();
+        let entry = Entry::new(options);
+        if entry.is_default() {
+            entries.remove(&id);
+        } else {
+            entries.insert(id, entry);
+        }
+    }
+
+    /// Resets the options of the hotkey with this id to the default ones.
+    pub fn remove_options(&self, id: u32) {
+        self.entries.lock().unwrap().remove(&id);
+        self.held.lock().unwrap().remove(&id);
+    }
+
+    /// Resets the activation state of the hotkey with this id, keeping its options.
+    pub fn reset(&self, id: u32) {
+        if let Some(entry) = self.entries.lock().unwrap().get_mut(&id) {
+            *entry = Entry::new(entry.options);
+        }
+    }
+
+    /// Releases the hotkey with this id as its grab is released, by a pause or when it is disabled.
+    ///
+    /// The platform won't report the release of a parked hotkey, so one that is held is
+    /// released right away, which emits [`HotKeyState::Released`] or [`HotKeyState::Cancelled`]
+    /// according to its [`Activation`]. Its activation state is then reset.
+    pub fn park(self: &Arc<Self>, id: u32) {
+        if let Some(event) = self.parked(id, Instant::now()) {
+            GlobalHotKeyEvent::emit(event);
+        }
+    }
+
+    /// Parks every held hotkey and sequence, see [`Activations::park`].
+    pub fn park_all(self: &Arc<Self>) {
+        let held: Vec<u32> = self.held.lock().unwrap().iter().copied().collect();
+        for id in held {
+            self.park(id);
+        }
+    }
+
+    /// Turns the parking of a hotkey into the release to emit, if it is held.
+    fn parked(self: &Arc<Self>, id: u32, now: Instant) -> Option<GlobalHotKeyEvent> {
+        let held = self.held.lock().unwrap().contains(&id);
+        let event = held
+            .then(|| self.dispatch(GlobalHotKeyEvent::new(id, HotKeyState::Released), now))
+            .flatten();
+        self.reset(id);
+        event
+    }
+
+    /// Moves the options of the hotkey with the id `from` to the one with the id `to`,
+    /// which starts out released.
+    pub fn move_options(&self, from: u32, to: u32) {
+        self.held.lock().unwrap().remove(&from);
+        let mut entries = self.entries.lock().unwrap();
+        if let Some(entry) = entries.remove(&from) {
+            entries.insert(to, Entry::new(entry.options));
+        }
+    }
+
+    /// Resets the options of every hotkey, which also stops the hold timer.
+    pub fn clear(&self) {
+        self.held.lock().unwrap().clear();
+        self.entries.lock().unwrap().clear();
+        self.timer.notify_one();
+    }
+
+    /// Sends a raw press, repeat or release reported by a platform implementation,
+    /// which is emitted according to the hotkey's [`Activation`].
+    pub fn send(self: &Arc<Self>, event: GlobalHotKeyEvent) {
+        if let Some(event) = self.dispatch(event, Instant::now()) {
+            GlobalHotKeyEvent::emit(event);
+        }
+    }
+
+    /// Turns a raw press, repeat or release received at `now` into the event to emit.
+    ///
+    /// Repeats are only emitted for the hotkeys registered with [`HotKeyOptions::with_repeat`].
+    fn dispatch(
+        self: &Arc<Self>,
+        event: GlobalHotKeyEvent,
+        now: Instant,
+    ) -> Option<GlobalHotKeyEvent> {
+        match event.state {
+            HotKeyState::Pressed => self.held.lock().unwrap().insert(event.id),
+            HotKeyState::Released => self.held.lock().unwrap().remove(&event.id),
+            _ => false,
+        };
+        let mut entries = self.entries.lock().unwrap();
+        let Some(entry) = entries.get_mut(&event.id) else {
+            return (!matches!(event.state, HotKeyState::Repeat(_))).then_some(event);
+        };
+
+        let (event, hold_started) = entry.handle(event, now);
+        if hold_started {
+            if self.timer_running.swap(true, Ordering::Relaxed) {
+                self.timer.notify_one();
+            } else {
+                let activations = self.clone();
+                std::thread::spawn(move || activations.run_hold_timer());
+            }
+        }
+        event
+    }
+
+    /// Emits the progress and activation events of every held hotkey, on a single thread
+    /// that stops once no hotkey is held, or its manager is dropped.
+    fn run_hold_timer(&self) {
+        let mut entries = self.entries.lock().unwrap();
+        loop {
+            let now = Instant::now();
+            let events: Vec<GlobalHotKeyEvent> = entries
+                .iter_mut()
+                .filter_map(|(&id, entry)| match &mut entry.tracker {
+                    Tracker::Hold(hold) => hold.tick(now).map(|s| GlobalHotKeyEvent::new(id, s)),
+                    _ => None,
+                })
+                .collect();
+            if !events.is_empty() {
+                // event handlers may register hotkeys, which locks the entries
+                drop(entries);
+                for event in events {
+                    GlobalHotKeyEvent::emit(event);
+                }
+                entries = self.entries.lock().unwrap();
+                continue;
+            }
+
+            let next_tick = entries
+                .values()
+                .filter_map(|entry| match &entry.tracker {
+                    Tracker::Hold(hold) => hold.next_tick(),
+                    _ => None,
+                })
+                .min();
+            let Some(next_tick) = next_tick else {
+                self.timer_running.store(false, Ordering::Relaxed);
+                return;
+            };
+            let timeout = next_tick.saturating_duration_since(now);
+            entries = self.timer.wait_timeout(entries, timeout).unwrap().0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn taps_within_window() {
+        let start = Instant::now();
+        let ms = |ms| start + Duration::from_millis(ms);
+        let mut taps = TapTracker::new(2, Duration::from_millis(300));
+
+        assert_eq!(taps.press(ms(0)), None);
+        assert_eq!(taps.release(), None);
+        assert_eq!(taps.press(ms(200)), Some(2));
+        assert_eq!(taps.release(), Some(2));
+        assert_eq!(taps.release(), None);
+
+        // a third press starts counting again
+        assert_eq!(taps.press(ms(300)), None);
+        assert_eq!(taps.press(ms(450)), Some(2));
+    }
+
+    #[test]
+    fn taps_outside_window() {
+        let start = Instant::now();
+        let ms = |ms| start + Duration::from_millis(ms);
+        let mut taps = TapTracker::new(3, Duration::from_millis(300));
+
+        assert_eq!(taps.press(ms(0)), None);
+        assert_eq!(taps.press(ms(250)), None);
+        // too late, this is the first tap of a new series
+        assert_eq!(taps.press(ms(600)), None);
+        assert_eq!(taps.press(ms(800)), None);
+        assert_eq!(taps.press(ms(1000)), Some(3));
+    }
+
+    #[test]
+    fn hold_fires_after_duration() {
+        let start = Instant::now();
+        let ms = |ms| start + Duration::from_millis(ms);
+        let mut hold = HoldTracker::new(Duration::from_millis(1000), None);
+
+        assert!(hold.press(ms(0)));
+        assert!(!hold.press(ms(30)));
+        assert_eq!(hold.next_tick(), Some(ms(1000)));
+        assert_eq!(hold.tick(ms(500)), None);
+        assert_eq!(hold.tick(ms(1000)), Some(HotKeyState::Pressed));
+        assert_eq!(hold.next_tick(), None);
+        assert_eq!(hold.release(), Some(HotKeyState::Released));
+        assert_eq!(hold.release(), None);
+    }
+
+    #[test]
+    fn hold_progress_and_cancel() {
+        let start = Instant::now();
+        let ms = |ms| start + Duration::from_millis(ms);
+        let mut hold = HoldTracker::new(
+            Duration::from_millis(1000),
+            Some(Duration::from_millis(300)),
+        );
+
+        assert!(hold.press(ms(0)));
+        assert_eq!(hold.next_tick(), Some(ms(300)));
+        assert_eq!(
+            hold.tick(ms(300)),
+            Some(HotKeyState::Holding(Duration::from_millis(300)))
+        );
+        assert_eq!(hold.next_tick(), Some(ms(600)));
+        assert_eq!(hold.release(), Some(HotKeyState::Cancelled));
+        assert_eq!(hold.next_tick(), None);
+        assert_eq!(hold.tick(ms(1000)), None);
+
+        // the next press is timed from its own start
+        assert!(hold.press(ms(1100)));
+        assert_eq!(hold.tick(ms(1300)), None);
+        assert_eq!(hold.next_tick(), Some(ms(1400)));
+    }
+
+    #[test]
+    fn repeats_are_opt_in() {
+        let now = Instant::now();
+        let event = |state| GlobalHotKeyEvent::new(1, state);
+
+        let mut entry = Entry::new(HotKeyOptions::new().with_repeat(true));
+        assert_eq!(
+            entry.handle(event(HotKeyState::Pressed), now).0,
+            Some(event(HotKeyState::Pressed))
+        );
+        assert_eq!(
+            entry.handle(event(HotKeyState::Repeat(0)), now).0,
+            Some(event(HotKeyState::Repeat(1)))
+        );
+        assert_eq!(
+            entry.handle(event(HotKeyState::Repeat(0)), now).0,
+            Some(event(HotKeyState::Repeat(2)))
+        );
+        entry.handle(event(HotKeyState::Released), now);
+        entry.handle(event(HotKeyState::Pressed), now);
+        assert_eq!(
+            entry.handle(event(HotKeyState::Repeat(0)), now).0,
+            Some(event(HotKeyState::Repeat(1)))
+        );
+
+        let mut entry = Entry::new(HotKeyOptions::new());
+        entry.handle(event(HotKeyState::Pressed), now);
+        assert_eq!(entry.handle(event(HotKeyState::Repeat(0)), now).0, None);
+
+        // no repeats until a hold fired
+        let mut entry = Entry::new(
+            HotKeyOptions::new()
+                .with_activation(Activation::Hold {
+                    duration: Duration::from_secs(1),
+                    progress: None,
+                })
+                .with_repeat(true),
+        );
+        assert_eq!(entry.handle(event(HotKeyState::Pressed), now), (None, true));
+        assert_eq!(entry.handle(event(HotKeyState::Repeat(0)), now).0, None);
+    }
+
+    #[test]
+    fn holds_share_one_timer() {
+        let now = Instant::now();
+        let hold = HotKeyOptions::new().with_activation(Activation::Hold {
+            duration: Duration::from_secs(60),
+            progress: None,
+        });
+        let activations = Arc::new(Activations::default());
+        activations.set_options(1, hold);
+        activations.set_options(2, hold);
+
+        activations.dispatch(GlobalHotKeyEvent::new(1, HotKeyState::Pressed), now);
+        activations.dispatch(GlobalHotKeyEvent::new(2, HotKeyState::Pressed), now);
+        assert!(activations.timer_running.load(Ordering::Relaxed));
+        // the timer holds the only other reference
+        assert_eq!(Arc::strong_count(&activations), 2);
+
+        // dropping the manager clears the options, which stops the timer
+        activations.clear();
+        let start = Instant::now();
+        while Arc::strong_count(&activations) != 1 {
+            assert!(start.elapsed() < Duration::from_secs(5));
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        assert!(!activations.timer_running.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn parking_releases_held_hotkeys() {
+        let now = Instant::now();
+        let press = |id| GlobalHotKeyEvent::new(id, HotKeyState::Pressed);
+        let hold = HotKeyOptions::new().with_activation(Activation::Hold {
+            duration: Duration::from_secs(60),
+            progress: None,
+        });
+        let activations = Arc::new(Activations::default());
+        activations.set_options(1, hold);
+
+        // a hold paused before it fired is cancelled, and no longer ticks
+        assert_eq!(activations.dispatch(press(1), now), None);
+        assert_eq!(
+            activations.parked(1, now),
+            Some(GlobalHotKeyEvent::new(1, HotKeyState::Cancelled))
+        );
+        let entries = activations.entries.lock().unwrap();
+        assert!(matches!(&entries[&1].tracker, Tracker::Hold(hold) if hold.next_tick().is_none()));
+        drop(entries);
+        // the next press after resuming starts a new hold
+        assert_eq!(activations.dispatch(press(1), now), None);
+        assert!(activations.timer_running.load(Ordering::Relaxed));
+        activations.parked(1, now);
+
+        // a hotkey without options is released, once
+        assert_eq!(activations.dispatch(press(2), now), Some(press(2)));
+        assert_eq!(
+            activations.parked(2, now),
+            Some(GlobalHotKeyEvent::new(2, HotKeyState::Released))
+        );
+        assert_eq!(activations.parked(2, now), None);
+        activations.clear();
+    }
+
+    #[test]
+    fn options_move_to_rebound_hotkey() {
+        let (old, new) = (1, 2);
+        let now = Instant::now();
+        let activations = Arc::new(Activations::default());
+        activations.set_options(old, HotKeyOptions::new().with_repeat(true));
+        activations.dispatch(GlobalHotKeyEvent::new(old, HotKeyState::Pressed), now);
+
+        activations.move_options(old, new);
+        let repeat = GlobalHotKeyEvent::new(old, HotKeyState::Repeat(0));
+        assert_eq!(activations.dispatch(repeat, now), None);
+
+        // the new hotkey starts out released
+        let repeat = GlobalHotKeyEvent::new(new, HotKeyState::Repeat(0));
+        activations.dispatch(GlobalHotKeyEvent::new(new, HotKeyState::Pressed), now);
+        assert_eq!(
+            activations.dispatch(repeat, now),
+            Some(GlobalHotKeyEvent::new(new, HotKeyState::Repeat(1)))
+        );
+    }
+
+    #[test]
+    fn options_are_per_manager() {
+        let now = Instant::now();
+        let double_tap = HotKeyOptions::new().with_activation(Activation::Taps {
+            count: 2,
+            window: Duration::from_millis(300),
+        });
+        let press = GlobalHotKeyEvent::new(1, HotKeyState::Pressed);
+
+        let first = Arc::new(Activations::default());
+        let second = Arc::new(Activations::default());
+        first.set_options(1, double_tap);
+        assert_eq!(first.dispatch(press, now), None);
+        assert_eq!(second.dispatch(press, now), Some(press));
+
+        first.clear();
+        assert_eq!(first.dispatch(press, now), Some(press));
+    }
+}
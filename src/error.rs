@@ -4,7 +4,7 @@
 
 use thiserror::Error;
 
-use crate::hotkey::HotKey;
+use crate::hotkey::{HotKey, HotKeyParseError, HotKeySequence};
 
 /// Errors returned by tray-icon.
 #[non_exhaustive]
@@ -12,20 +12,52 @@ use crate::hotkey::HotKey;
 pub enum Error {
     #[error(transparent)]
     OsError(#[from] std::io::Error),
-    #[error("{0}")]
-    HotKeyParseError(String),
-    #[error("Couldn't recognize \"{0}\" as a valid HotKey Code, if you feel like it should be, please report this to https://github.com/tauri-apps/global-hotkey")]
-    UnrecognizedHotKeyCode(String),
-    #[error("Unexpected empty token while parsing hotkey: \"{0}\"")]
-    EmptyHotKeyToken(String),
-    #[error("Unexpected hotkey string format: \"{0}\", a hotkey should have the modifiers first and only contain one main key")]
-    UnexpectedHotKeyFormat(String),
-    #[error("{0}")]
-    FailedToRegister(String),
-    #[error("Failed to unregister hotkey: {0:?}")]
-    FailedToUnRegister(HotKey),
+    #[error(transparent)]
+    HotKeyParseError(#[from] HotKeyParseError),
+    #[error("Failed to register hotkey {hotkey}: {reason}")]
+    FailedToRegister {
+        hotkey: HotKey,
+        reason: FailureReason,
+    },
+    #[error("Failed to unregister hotkey {hotkey}: {reason}")]
+    FailedToUnRegister {
+        hotkey: HotKey,
+        reason: FailureReason,
+    },
     #[error("HotKey already registerd: {0:?}")]
     AlreadyRegistered(HotKey),
+    #[error("HotKey sequence already registerd: {0:?}")]
+    SequenceAlreadyRegistered(HotKeySequence),
+}
+
+/// Why a hotkey couldn't be registered or unregistered,
+/// see [`Error::FailedToRegister`] and [`Error::FailedToUnRegister`].
+#[non_exhaustive]
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FailureReason {
+    /// The platform has no key for the trigger of the hotkey, like a character missing from
+    /// the keyboard layout or a [`Code`](keyboard_types::Code) it can't bind here, like `Fn`,
+    /// or can't grab this kind of trigger, like a mouse button on Windows.
+    #[error("the key is not supported on this platform")]
+    UnsupportedKey,
+    /// The platform can't grab the modifiers of the hotkey, like Hyper on Windows
+    /// or a modifier the X11 keymap doesn't map.
+    #[error("the modifiers are not supported on this platform")]
+    UnsupportedModifiers,
+    /// Another application, or another X11 client, already grabbed this combination.
+    #[error("the combination is grabbed by another application")]
+    GrabbedByOtherClient,
+    /// The platform backend can't be used, like when no X server can be reached
+    /// or it lacks the XInput2 extension modifier-only hotkeys need.
+    #[error("the platform backend is unavailable")]
+    BackendUnavailable,
+    /// The hotkey isn't registered.
+    #[error("the hotkey is not registered")]
+    NotRegistered,
+    /// Another error reported by the OS, with its raw error code:
+    /// the `GetLastError` code on Windows, the `OSStatus` on macOS and the X11 error code on Linux.
+    #[error("OS error {0}")]
+    Os(i32),
 }
 
 /// Convenient type alias of Result type for tray-icon.
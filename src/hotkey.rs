@@ -26,90 +26,452 @@
 //! # assert!("shift+KeyQ+alt".parse::<HotKey>().is_err());
 //! ```
 //!
+//! Modifiers can be restricted to their left or right key using [`ModifierSides`](crate::hotkey::ModifierSides),
+//! `RCtrl+Space` and `LCtrl+Space` are two different hotkeys while `Ctrl+Space` matches both.
+//! ```no_run
+//! # use global_hotkey::hotkey::{HotKey, ModifierSides, Code};
+//! let hotkey = HotKey::new(None, Code::Space).with_sides(ModifierSides::CONTROL_RIGHT);
+//! assert_eq!(hotkey, "RCtrl+Space".parse().unwrap());
+//! ```
+//!
+//! Keys named after their [`Code`](crate::hotkey::Code), like `KeyZ` or `Code(KeyZ)`, are bound by their
+//! physical position on the keyboard, whatever the layout. A single character, like `z`, binds the key
+//! producing that character in the current keyboard layout, see [`Trigger`](crate::hotkey::Trigger).
+//! ```no_run
+//! # use global_hotkey::hotkey::{HotKey, Modifiers, Code, Trigger};
+//! // the key next to Tab on a QWERTY keyboard, labelled A on AZERTY keyboards
+//! let physical: HotKey = "Ctrl+Code(KeyQ)".parse().unwrap();
+//! assert_eq!(physical, HotKey::new(Some(Modifiers::CONTROL), Code::KeyQ));
+//! // the key labelled Q, whatever its position
+//! let character: HotKey = "Ctrl+q".parse().unwrap();
+//! assert_eq!(character.trigger(), Trigger::Character('q'));
+//! ```
+//!
+//! A hotkey can also consist of modifiers only, see [`Trigger::ModifiersOnly`](crate::hotkey::Trigger::ModifiersOnly).
+//! ```no_run
+//! # use global_hotkey::hotkey::{HotKey, Modifiers};
+//! let hotkey = HotKey::modifiers_only(Modifiers::CONTROL | Modifiers::SHIFT);
+//! assert_eq!(hotkey, "Ctrl+Shift".parse().unwrap());
+//! ```
+//!
+//! Mouse buttons and scroll wheel directions can be used instead of a key, see [`Trigger::Mouse`](crate::hotkey::Trigger::Mouse)
+//! and [`Trigger::Wheel`](crate::hotkey::Trigger::Wheel).
+//! ```no_run
+//! # use global_hotkey::hotkey::{HotKey, Modifiers, MouseButton, WheelDirection};
+//! let hotkey: HotKey = "Ctrl+Mouse4".parse().unwrap();
+//! assert_eq!(hotkey, HotKey::from_trigger(Some(Modifiers::CONTROL), MouseButton::Back));
+//! let hotkey: HotKey = "Super+WheelUp".parse().unwrap();
+//! assert_eq!(hotkey, HotKey::from_trigger(Some(Modifiers::SUPER), WheelDirection::Up));
+//! ```
+//!
+//! GTK accelerators, like `<Primary><Shift>q`, are read and written by
+//! [`HotKey::from_gtk_accelerator`](crate::hotkey::HotKey::from_gtk_accelerator)
+//! and [`HotKey::to_gtk_accelerator`](crate::hotkey::HotKey::to_gtk_accelerator).
+//! Vim keys, like `<C-S-a>`, and Emacs keys, like `C-M-%`, are read by
+//! [`HotKey::from_vim`](crate::hotkey::HotKey::from_vim) and [`HotKey::from_emacs`](crate::hotkey::HotKey::from_emacs).
+//!
+//! Multi-stroke hotkeys, like `Ctrl+K Ctrl+C`, are described by a [`HotKeySequence`](crate::hotkey::HotKeySequence).
+//! ```no_run
+//! # use global_hotkey::hotkey::HotKeySequence;
+//! let sequence: HotKeySequence = "Ctrl+K Ctrl+C".parse().unwrap();
+//! ```
+//!
 
 pub use keyboard_types::{Code, Modifiers};
 use std::{borrow::Borrow, hash::Hash, str::FromStr};
 
-#[cfg(target_os = "macos")]
-pub const CMD_OR_CTRL: Modifiers = Modifiers::SUPER;
-#[cfg(not(target_os = "macos"))]
-pub const CMD_OR_CTRL: Modifiers = Modifiers::CONTROL;
+mod diagnostics;
+mod gtk;
+mod label;
+mod locale;
+mod notation;
+mod sequence;
+#[cfg(feature = "serde")]
+mod serialize;
+
+pub use diagnostics::{HotKeyParseError, HotKeyParseErrorKind};
+pub use label::LabelStyle;
+pub use locale::HotKeyLocale;
+pub use sequence::HotKeySequence;
+pub(crate) use sequence::SequenceTracker;
+
+/// The modifier `CmdOrCtrl` stands for on the current platform, Super on macOS and Control elsewhere.
+pub const CMD_OR_CTRL: Modifiers = LabelStyle::current().cmd_or_ctrl();
+
+bitflags::bitflags! {
+    /// Restricts the modifiers of a [`HotKey`] to their left or right key.
+    ///
+    /// A modifier of a [`HotKey`] without a side matches both of its keys.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct ModifierSides: u8 {
+        const SHIFT_LEFT = 1 << 0;
+        const SHIFT_RIGHT = 1 << 1;
+        const CONTROL_LEFT = 1 << 2;
+        const CONTROL_RIGHT = 1 << 3;
+        const ALT_LEFT = 1 << 4;
+        const ALT_RIGHT = 1 << 5;
+        const SUPER_LEFT = 1 << 6;
+        const SUPER_RIGHT = 1 << 7;
+    }
+}
+
+/// The left and right [`ModifierSides`] of each modifier that has two keys.
+const MODIFIER_SIDES: [(Modifiers, ModifierSides, ModifierSides); 4] = [
+    (
+        Modifiers::SHIFT,
+        ModifierSides::SHIFT_LEFT,
+        ModifierSides::SHIFT_RIGHT,
+    ),
+    (
+        Modifiers::CONTROL,
+        ModifierSides::CONTROL_LEFT,
+        ModifierSides::CONTROL_RIGHT,
+    ),
+    (
+        Modifiers::ALT,
+        ModifierSides::ALT_LEFT,
+        ModifierSides::ALT_RIGHT,
+    ),
+    (
+        Modifiers::SUPER,
+        ModifierSides::SUPER_LEFT,
+        ModifierSides::SUPER_RIGHT,
+    ),
+];
+
+impl ModifierSides {
+    /// Returns `true` if the `pressed` modifier keys satisfy the sides required by `self`.
+    pub(crate) fn satisfied_by(self, pressed: ModifierSides) -> bool {
+        MODIFIER_SIDES.iter().all(|&(_, left, right)| {
+            let required = self & (left | right);
+            required.is_empty() || pressed.intersects(required)
+        })
+    }
+}
+
+/// What has to be pressed, besides the modifiers, to trigger a [`HotKey`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum Trigger {
+    /// A key, identified by its physical position on the keyboard.
+    Key(Code),
+    /// The key producing this character in the keyboard layout active when the hotkey is
+    /// registered, like [`Key::Character`](keyboard_types::Key::Character).
+    ///
+    /// The modifiers needed to type the character, like Shift for uppercase letters,
+    /// are not implied. Letters are stored in lowercase, `HotKey::from_trigger(None, 'Q')`
+    /// is the same as `HotKey::from_trigger(None, 'q')`.
+    ///
+    /// If no key of the current layout produces an ASCII letter, digit or punctuation character,
+    /// the key at its position on a US keyboard is used instead.
+    Character(char),
+    /// Nothing but the modifiers of the hotkey.
+    ///
+    /// The hotkey triggers when its modifiers are pressed and then released without
+    /// any other key being pressed in between, [`HotKeyState::Pressed`](crate::HotKeyState::Pressed)
+    /// and [`HotKeyState::Released`](crate::HotKeyState::Released) are both sent
+    /// once the first modifier is released.
+    ///
+    /// ## Platform-specific:
+    ///
+    /// - **Windows / macOS**: Unsupported.
+    ModifiersOnly,
+    /// A mouse button, sending [`HotKeyState::Pressed`](crate::HotKeyState::Pressed) and
+    /// [`HotKeyState::Released`](crate::HotKeyState::Released) like a key.
+    ///
+    /// ## Platform-specific:
+    ///
+    /// - **Windows / macOS**: Unsupported.
+    Mouse(MouseButton),
+    /// A notch of the scroll wheel in one direction, sending a single
+    /// [`HotKeyState::Scrolled`](crate::HotKeyState::Scrolled) per notch.
+    ///
+    /// ## Platform-specific:
+    ///
+    /// - **Windows / macOS**: Unsupported.
+    Wheel(WheelDirection),
+}
+
+/// A mouse button, see [`Trigger::Mouse`].
+///
+/// Buttons are numbered like in most games, `Mouse1` is [`MouseButton::Left`],
+/// `Mouse3` is [`MouseButton::Middle`] and `Mouse4` is [`MouseButton::Back`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+    /// The first side button, usually navigating back.
+    Back,
+    /// The second side button, usually navigating forward.
+    Forward,
+    /// Any other button, by its number, starting at `6`.
+    ///
+    /// Hotkeys turn the smaller numbers into the buttons above, `Other(1)` is [`MouseButton::Left`].
+    Other(u8),
+}
+
+impl MouseButton {
+    /// Returns the number of this button, `Mouse1` being [`MouseButton::Left`].
+    pub const fn number(self) -> u8 {
+        match self {
+            Self::Left => 1,
+            Self::Right => 2,
+            Self::Middle => 3,
+            Self::Back => 4,
+            Self::Forward => 5,
+            Self::Other(n) => n,
+        }
+    }
+
+    /// Returns the button with this number, `Mouse1` being [`MouseButton::Left`].
+    pub const fn from_number(number: u8) -> Option<Self> {
+        Some(match number {
+            0 => return None,
+            1 => Self::Left,
+            2 => Self::Right,
+            3 => Self::Middle,
+            4 => Self::Back,
+            5 => Self::Forward,
+            n => Self::Other(n),
+        })
+    }
+}
 
-#[derive(thiserror::Error, Debug)]
-pub enum HotKeyParseError {
-    #[error("Couldn't recognize \"{0}\" as a valid key for hotkey, if you feel like it should be, please report this to https://github.com/tauri-apps/muda")]
-    UnsupportedKey(String),
-    #[error("Found empty token while parsing hotkey: {0}")]
-    EmptyToken(String),
-    #[error("Invalid hotkey format: \"{0}\", an hotkey should have the modifiers first and only one main key, for example: \"Shift + Alt + K\"")]
-    InvalidFormat(String),
+/// A direction of the scroll wheel, see [`Trigger::Wheel`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WheelDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl From<MouseButton> for Trigger {
+    fn from(button: MouseButton) -> Self {
+        Self::Mouse(button)
+    }
+}
+
+impl From<WheelDirection> for Trigger {
+    fn from(direction: WheelDirection) -> Self {
+        Self::Wheel(direction)
+    }
+}
+
+impl From<Code> for Trigger {
+    fn from(key: Code) -> Self {
+        Self::Key(key)
+    }
+}
+
+impl From<char> for Trigger {
+    fn from(c: char) -> Self {
+        Self::Character(c)
+    }
+}
+
+impl Trigger {
+    /// Returns the key at the position of this character on a US keyboard,
+    /// used when no key of the current layout produces it.
+    pub(crate) fn fallback_key(c: char) -> Option<Code> {
+        if c.is_ascii_graphic() {
+            parse_key(&c.to_string())
+        } else {
+            None
+        }
+    }
 }
 
 /// A keyboard shortcut that consists of an optional combination
 /// of modifier keys (provided by [`Modifiers`](crate::hotkey::Modifiers)) and
-/// one key ([`Code`](crate::hotkey::Code)).
+/// one key ([`Code`](crate::hotkey::Code)), or only modifiers.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct HotKey {
     pub(crate) mods: Modifiers,
-    pub(crate) key: Code,
+    pub(crate) sides: ModifierSides,
+    pub(crate) trigger: Trigger,
     id: u32,
 }
 
 impl HotKey {
     /// Creates a new hotkey to define keyboard shortcuts throughout your application.
-    /// Only [`Modifiers::ALT`], [`Modifiers::SHIFT`], [`Modifiers::CONTROL`], and [`Modifiers::SUPER`]
-    pub fn new(mods: Option<Modifiers>, key: Code) -> Self {
-        let mut mods = mods.unwrap_or_else(Modifiers::empty);
-        if mods.contains(Modifiers::META) {
-            mods.remove(Modifiers::META);
-            mods.insert(Modifiers::SUPER);
+    ///
+    /// Only [`Modifiers::ALT`], [`Modifiers::SHIFT`], [`Modifiers::CONTROL`], [`Modifiers::SUPER`],
+    /// [`Modifiers::META`], [`Modifiers::HYPER`], [`Modifiers::ALT_GRAPH`] and [`Modifiers::CAPS_LOCK`]
+    /// are supported.
+    ///
+    /// It can be used in const contexts, like `const SAVE: HotKey = HotKey::new(..)`.
+    pub const fn new(mods: Option<Modifiers>, key: Code) -> Self {
+        let mods = match mods {
+            Some(mods) => mods,
+            None => Modifiers::empty(),
+        };
+        Self::from_parsed(mods, ModifierSides::empty(), Trigger::Key(key))
+    }
+
+    /// Creates a new hotkey that triggers when `mods` are pressed and released alone,
+    /// see [`Trigger::ModifiersOnly`].
+    pub const fn modifiers_only(mods: Modifiers) -> Self {
+        Self::from_parsed(mods, ModifierSides::empty(), Trigger::ModifiersOnly)
+    }
+
+    /// Creates a hotkey from parts that are already normalized: the sides must be
+    /// of `mods` and a [`Trigger::Character`] lowercase.
+    ///
+    /// Used by the `hotkey!` macro of the `global-hotkey-macros` crate, which parses the hotkey
+    /// at compile time.
+    #[doc(hidden)]
+    pub const fn from_parsed(mods: Modifiers, sides: ModifierSides, trigger: Trigger) -> Self {
+        // `Other(1)` is the left button, it must be equal to it and share its id
+        let trigger = match trigger {
+            Trigger::Mouse(button) => match MouseButton::from_number(button.number()) {
+                Some(button) => Trigger::Mouse(button),
+                None => trigger,
+            },
+            trigger => trigger,
+        };
+        Self {
+            mods,
+            sides,
+            trigger,
+            id: Self::generate_hash(mods, sides, trigger),
         }
+    }
 
-        let id = Self::generate_hash(mods, key);
+    /// Creates a new hotkey from its modifiers and [`Trigger`].
+    pub fn from_trigger(mods: Option<Modifiers>, trigger: impl Into<Trigger>) -> Self {
+        let mods = mods.unwrap_or_else(Modifiers::empty);
 
-        Self { mods, key, id }
+        let trigger = match trigger.into() {
+            Trigger::Character(c) => Trigger::Character(c.to_lowercase().next().unwrap_or(c)),
+            trigger => trigger,
+        };
+        Self::from_parsed(mods, ModifierSides::empty(), trigger)
     }
 
-    fn generate_hash(mods: Modifiers, key: Code) -> u32 {
-        let mut hotkey_str = String::new();
-        if mods.contains(Modifiers::SHIFT) {
-            hotkey_str.push_str("shift+")
-        }
-        if mods.contains(Modifiers::CONTROL) {
-            hotkey_str.push_str("control+")
-        }
-        if mods.contains(Modifiers::ALT) {
-            hotkey_str.push_str("alt+")
-        }
-        if mods.contains(Modifiers::SUPER) {
-            hotkey_str.push_str("super+")
+    /// Restricts the modifiers of this hotkey to their left or right key,
+    /// replacing the sides set previously.
+    ///
+    /// The modifiers of `sides` are added to the hotkey's modifiers if missing.
+    /// Giving both sides of a modifier is the same as giving none, and matches either key.
+    pub const fn with_sides(self, sides: ModifierSides) -> Self {
+        let mut mods = self.mods;
+        let mut own_sides = ModifierSides::empty();
+        // `for` loops can't run in const contexts
+        let mut i = 0;
+        while i < MODIFIER_SIDES.len() {
+            let (modifier, left, right) = MODIFIER_SIDES[i];
+            let side = sides.intersection(left.union(right));
+            if !side.is_empty() {
+                mods = mods.union(modifier);
+            }
+            if side.bits() == left.bits() || side.bits() == right.bits() {
+                own_sides = own_sides.union(side);
+            }
+            i += 1;
         }
-        hotkey_str.push_str(&key.to_string());
 
-        let mut hasher = std::collections::hash_map::DefaultHasher::new();
-        hotkey_str.hash(&mut hasher);
-        std::hash::Hasher::finish(&hasher) as u32
+        Self::from_parsed(mods, own_sides, self.trigger)
+    }
+
+    /// Hashes the parts of a hotkey with FNV-1a, which unlike `DefaultHasher` runs in const contexts.
+    const fn generate_hash(mods: Modifiers, sides: ModifierSides, trigger: Trigger) -> u32 {
+        let (kind, value) = match trigger {
+            Trigger::ModifiersOnly => (0, 0),
+            Trigger::Key(key) => (1, key as u32),
+            Trigger::Character(c) => (2, c as u32),
+            Trigger::Mouse(button) => (3, button.number() as u32),
+            Trigger::Wheel(direction) => (4, direction as u32),
+        };
+        let words = [mods.bits(), sides.bits() as u32, kind, value];
+
+        let mut hash = FNV_OFFSET_BASIS;
+        let mut i = 0;
+        while i < words.len() {
+            hash = fnv1a(hash, &words[i].to_le_bytes());
+            i += 1;
+        }
+        hash
     }
 
     /// Returns the id associated with this hotKey
-    /// which is a hash of the string represention of modifiers and key within this hotKey.
-    pub fn id(&self) -> u32 {
+    /// which is a hash of the modifiers, sides and trigger of this hotKey.
+    pub const fn id(&self) -> u32 {
         self.id
     }
 
+    /// Returns the modifiers of this hotkey.
+    pub const fn mods(&self) -> Modifiers {
+        self.mods
+    }
+
+    /// Returns what has to be pressed, besides the modifiers, to trigger this hotkey.
+    pub const fn trigger(&self) -> Trigger {
+        self.trigger
+    }
+
+    /// Returns the key of this hotkey, or `None` if it isn't triggered by a key.
+    pub fn key(&self) -> Option<Code> {
+        match self.trigger {
+            Trigger::Key(key) => Some(key),
+            _ => None,
+        }
+    }
+
+    /// Returns the [`ModifierSides`] this hotkey is restricted to.
+    pub const fn sides(&self) -> ModifierSides {
+        self.sides
+    }
+
     /// Returns `true` if this [`Code`] and [`Modifiers`] matches this hotkey.
+    ///
+    /// Hotkeys bound to a [`Trigger::Character`] match the key at its position on a US keyboard,
+    /// so `"Ctrl+K".parse()` matches [`Code::KeyK`] like hotkeys parsed before characters
+    /// could be bound.
+    ///
+    /// The [`ModifierSides`] of this hotkey are ignored, see [`HotKey::matches_with_sides`].
     pub fn matches(&self, modifiers: impl Borrow<Modifiers>, key: impl Borrow<Code>) -> bool {
         // Should be a const but const bit_or doesn't work here.
-        let base_mods = Modifiers::SHIFT | Modifiers::CONTROL | Modifiers::ALT | Modifiers::SUPER;
+        let base_mods = Modifiers::SHIFT
+            | Modifiers::CONTROL
+            | Modifiers::ALT
+            | Modifiers::SUPER
+            | Modifiers::META
+            | Modifiers::HYPER
+            | Modifiers::ALT_GRAPH;
+        // CapsLock is only compared when required, since it stays on once pressed
+        let base_mods = base_mods | (self.mods & Modifiers::CAPS_LOCK);
         let modifiers = modifiers.borrow();
         let key = key.borrow();
-        self.mods == *modifiers & base_mods && self.key == *key
+        let trigger = match self.trigger {
+            Trigger::Character(c) => Trigger::fallback_key(c).map(Trigger::Key),
+            trigger => Some(trigger),
+        };
+        self.mods == *modifiers & base_mods && trigger == Some(Trigger::Key(*key))
+    }
+
+    /// Returns `true` if this [`Code`] and [`Modifiers`] matches this hotkey
+    /// and the pressed modifier keys, `sides`, are on the sides required by this hotkey.
+    pub fn matches_with_sides(
+        &self,
+        modifiers: impl Borrow<Modifiers>,
+        sides: impl Borrow<ModifierSides>,
+        key: impl Borrow<Code>,
+    ) -> bool {
+        self.matches(modifiers, key) && self.sides.satisfied_by(*sides.borrow())
     }
 }
 
 // HotKey::from_str is available to be backward
 // compatible with tauri and it also open the option
-// to generate hotkey from string
+// to generate hotkey from string, single characters
+// like `K` still match `Code::KeyK` in `HotKey::matches`
 impl FromStr for HotKey {
     type Err = HotKeyParseError;
     fn from_str(hotkey_string: &str) -> Result<Self, Self::Err> {
@@ -117,6 +479,79 @@ impl FromStr for HotKey {
     }
 }
 
+/// Writes the canonical form of a hotkey, which parses back to an equal [`HotKey`].
+///
+/// Modifiers come first in a fixed order, then the key by its [`Code`] name, like `Shift+Control+KeyK`.
+/// The `+` character is written `Plus` and characters that can't be written as is, like a space,
+/// are written by their codepoint, like `Char(0x20)`.
+///
+/// The only hotkeys that don't round-trip are the modifier-only hotkeys without modifiers,
+/// and the one made of the CapsLock modifier alone, which is written like the CapsLock key.
+impl std::fmt::Display for HotKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let side = |left: ModifierSides, right: ModifierSides| {
+            if self.sides.contains(left) {
+                "L"
+            } else if self.sides.contains(right) {
+                "R"
+            } else {
+                ""
+            }
+        };
+        // CapsLock comes first as it is the CapsLock key when it comes last
+        let modifiers = [
+            (Modifiers::CAPS_LOCK, "", "CapsLock"),
+            (
+                Modifiers::SHIFT,
+                side(ModifierSides::SHIFT_LEFT, ModifierSides::SHIFT_RIGHT),
+                "Shift",
+            ),
+            (
+                Modifiers::CONTROL,
+                side(ModifierSides::CONTROL_LEFT, ModifierSides::CONTROL_RIGHT),
+                "Control",
+            ),
+            (
+                Modifiers::ALT,
+                side(ModifierSides::ALT_LEFT, ModifierSides::ALT_RIGHT),
+                "Alt",
+            ),
+            (
+                Modifiers::SUPER,
+                side(ModifierSides::SUPER_LEFT, ModifierSides::SUPER_RIGHT),
+                "Super",
+            ),
+            (Modifiers::META, "", "Meta"),
+            (Modifiers::HYPER, "", "Hyper"),
+            (Modifiers::ALT_GRAPH, "", "AltGraph"),
+        ];
+
+        let mut separator = "";
+        for (modifier, side, name) in modifiers {
+            if self.mods.contains(modifier) {
+                write!(f, "{separator}{side}{name}")?;
+                separator = "+";
+            }
+        }
+
+        match self.trigger {
+            // keys named like a modifier, like `ShiftLeft`, would parse back as the modifier
+            Trigger::Key(key) if parse_modifier(&key.to_string()).is_some() => {
+                write!(f, "{separator}Code({key})")
+            }
+            Trigger::Key(key) => write!(f, "{separator}{key}"),
+            Trigger::Character('+') => write!(f, "{separator}Plus"),
+            Trigger::Character(c) if c.is_whitespace() || c.is_control() => {
+                write!(f, "{separator}Char({:#X})", c as u32)
+            }
+            Trigger::Character(c) => write!(f, "{separator}{c}"),
+            Trigger::ModifiersOnly => Ok(()),
+            Trigger::Mouse(button) => write!(f, "{separator}Mouse{}", button.number()),
+            Trigger::Wheel(direction) => write!(f, "{separator}Wheel{:?}", direction),
+        }
+    }
+}
+
 impl TryFrom<&str> for HotKey {
     type Error = HotKeyParseError;
 
@@ -133,24 +568,80 @@ impl TryFrom<String> for HotKey {
     }
 }
 
+const FNV_OFFSET_BASIS: u32 = 0x811c_9dc5;
+
+/// Feeds `bytes` to a 32-bit FNV-1a `hash`, which gives the same ids on every platform and build.
+const fn fnv1a(mut hash: u32, bytes: &[u8]) -> u32 {
+    let mut i = 0;
+    while i < bytes.len() {
+        hash ^= bytes[i] as u32;
+        hash = hash.wrapping_mul(0x0100_0193);
+        i += 1;
+    }
+    hash
+}
+
 fn parse_hotkey(hotkey: &str) -> Result<HotKey, HotKeyParseError> {
-    let tokens = hotkey.split('+').collect::<Vec<&str>>();
+    parse_hotkey_with(hotkey, None)
+}
+
+/// Parses a hotkey, trying the names of `locale` before the English ones.
+fn parse_hotkey_with(
+    hotkey: &str,
+    locale: Option<&HotKeyLocale>,
+) -> Result<HotKey, HotKeyParseError> {
+    use HotKeyParseErrorKind::*;
+
+    let tokens = tokenize(hotkey);
+    let key_names = || {
+        let localized = locale.into_iter().flat_map(|locale| locale.key_names());
+        localized.chain(KEY_NAMES.iter().chain(MODIFIER_NAMES).copied())
+    };
+    let modifier_names = || {
+        let localized = locale
+            .into_iter()
+            .flat_map(|locale| locale.modifier_names());
+        localized.chain(MODIFIER_NAMES.iter().copied())
+    };
+    // `CapsLock` is a key when it comes last, like in `Ctrl+CapsLock`
+    let modifier = |token: &str, last: bool| {
+        let localized = locale.and_then(|locale| locale.modifier(token));
+        match localized {
+            Some((Modifiers::CAPS_LOCK, _)) if last => None,
+            Some(modifier) => Some(modifier),
+            None => match token.to_uppercase().as_str() {
+                "CAPSLOCK" if !last => Some((Modifiers::CAPS_LOCK, ModifierSides::empty())),
+                _ => parse_modifier(token),
+            },
+        }
+    };
+    let trigger = |token: &str| {
+        let localized = locale.and_then(|locale| locale.key(token));
+        localized.map(Trigger::Key).or_else(|| parse_trigger(token))
+    };
 
     let mut mods = Modifiers::empty();
+    let mut sides = ModifierSides::empty();
     let mut key = None;
 
     match tokens.len() {
         // single key hotkey
-        1 => {
-            key = Some(parse_key(tokens[0])?);
+        1 if modifier(tokens[0].trim(), true).is_none() => {
+            if tokens[0].is_empty() {
+                return Err(HotKeyParseError::new(EmptyToken, hotkey, tokens[0]));
+            }
+            key = Some(trigger(tokens[0]).ok_or_else(|| {
+                HotKeyParseError::new(UnsupportedKey, hotkey, tokens[0].trim()).suggest(key_names())
+            })?);
         }
-        // modifiers and key comobo hotkey
+        // modifiers and key comobo hotkey, or modifiers only hotkey
         _ => {
-            for raw in tokens {
+            let last = tokens.len() - 1;
+            for (i, raw) in tokens.iter().enumerate() {
                 let token = raw.trim();
 
                 if token.is_empty() {
-                    return Err(HotKeyParseError::EmptyToken(hotkey.to_string()));
+                    return Err(HotKeyParseError::new(EmptyToken, hotkey, token));
                 }
 
                 if key.is_some() {
@@ -160,159 +651,697 @@ fn parse_hotkey(hotkey: &str) -> Result<HotKey, HotKeyParseError> {
                     // examples:
                     // 1. "Ctrl+Shift+C+A" => only one main key should be allowd.
                     // 2. "Ctrl+C+Shift" => wrong order
-                    return Err(HotKeyParseError::InvalidFormat(hotkey.to_string()));
+                    return Err(HotKeyParseError::new(InvalidFormat, hotkey, token));
                 }
 
-                match token.to_uppercase().as_str() {
-                    "OPTION" | "ALT" => {
-                        mods |= Modifiers::ALT;
-                    }
-                    "CONTROL" | "CTRL" => {
-                        mods |= Modifiers::CONTROL;
-                    }
-                    "COMMAND" | "CMD" | "SUPER" => {
-                        mods |= Modifiers::SUPER;
-                    }
-                    "SHIFT" => {
-                        mods |= Modifiers::SHIFT;
-                    }
-                    #[cfg(target_os = "macos")]
-                    "COMMANDORCONTROL" | "COMMANDORCTRL" | "CMDORCTRL" | "CMDORCONTROL" => {
-                        mods |= Modifiers::SUPER;
-                    }
-                    #[cfg(not(target_os = "macos"))]
-                    "COMMANDORCONTROL" | "COMMANDORCTRL" | "CMDORCTRL" | "CMDORCONTROL" => {
-                        mods |= Modifiers::CONTROL;
+                match modifier(token, i == last) {
+                    Some((m, s)) => {
+                        mods |= m;
+                        sides |= s;
                     }
-                    _ => {
-                        key = Some(parse_key(token)?);
+                    None => {
+                        key = Some(trigger(token).ok_or_else(|| {
+                            // only modifiers come before the last token
+                            if i != last {
+                                HotKeyParseError::new(UnsupportedModifier, hotkey, token)
+                                    .suggest(modifier_names())
+                            } else {
+                                HotKeyParseError::new(UnsupportedKey, hotkey, token)
+                                    .suggest(key_names())
+                            }
+                        })?);
                     }
                 }
             }
         }
     }
 
-    Ok(HotKey::new(Some(mods), key.unwrap()))
+    let trigger = key.unwrap_or(Trigger::ModifiersOnly);
+    Ok(HotKey::from_trigger(Some(mods), trigger).with_sides(sides))
 }
 
-fn parse_key(key: &str) -> Result<Code, HotKeyParseError> {
-    use Code::*;
-    match key.to_uppercase().as_str() {
-        "BACKQUOTE" | "`" => Ok(Backquote),
-        "BACKSLASH" | "\\" => Ok(Backslash),
-        "BRACKETLEFT" | "[" => Ok(BracketLeft),
-        "BRACKETRIGHT" | "]" => Ok(BracketRight),
-        "COMMA" | "," => Ok(Comma),
-        "DIGIT0" | "0" => Ok(Digit0),
-        "DIGIT1" | "1" => Ok(Digit1),
-        "DIGIT2" | "2" => Ok(Digit2),
-        "DIGIT3" | "3" => Ok(Digit3),
-        "DIGIT4" | "4" => Ok(Digit4),
-        "DIGIT5" | "5" => Ok(Digit5),
-        "DIGIT6" | "6" => Ok(Digit6),
-        "DIGIT7" | "7" => Ok(Digit7),
-        "DIGIT8" | "8" => Ok(Digit8),
-        "DIGIT9" | "9" => Ok(Digit9),
-        "EQUAL" | "=" => Ok(Equal),
-        "KEYA" | "A" => Ok(KeyA),
-        "KEYB" | "B" => Ok(KeyB),
-        "KEYC" | "C" => Ok(KeyC),
-        "KEYD" | "D" => Ok(KeyD),
-        "KEYE" | "E" => Ok(KeyE),
-        "KEYF" | "F" => Ok(KeyF),
-        "KEYG" | "G" => Ok(KeyG),
-        "KEYH" | "H" => Ok(KeyH),
-        "KEYI" | "I" => Ok(KeyI),
-        "KEYJ" | "J" => Ok(KeyJ),
-        "KEYK" | "K" => Ok(KeyK),
-        "KEYL" | "L" => Ok(KeyL),
-        "KEYM" | "M" => Ok(KeyM),
-        "KEYN" | "N" => Ok(KeyN),
-        "KEYO" | "O" => Ok(KeyO),
-        "KEYP" | "P" => Ok(KeyP),
-        "KEYQ" | "Q" => Ok(KeyQ),
-        "KEYR" | "R" => Ok(KeyR),
-        "KEYS" | "S" => Ok(KeyS),
-        "KEYT" | "T" => Ok(KeyT),
-        "KEYU" | "U" => Ok(KeyU),
-        "KEYV" | "V" => Ok(KeyV),
-        "KEYW" | "W" => Ok(KeyW),
-        "KEYX" | "X" => Ok(KeyX),
-        "KEYY" | "Y" => Ok(KeyY),
-        "KEYZ" | "Z" => Ok(KeyZ),
-        "MINUS" | "-" => Ok(Minus),
-        "PERIOD" | "." => Ok(Period),
-        "QUOTE" | "'" => Ok(Quote),
-        "SEMICOLON" | ";" => Ok(Semicolon),
-        "SLASH" | "/" => Ok(Slash),
-        "BACKSPACE" => Ok(Backspace),
-        "CAPSLOCK" => Ok(CapsLock),
-        "ENTER" => Ok(Enter),
-        "SPACE" => Ok(Space),
-        "TAB" => Ok(Tab),
-        "DELETE" => Ok(Delete),
-        "END" => Ok(End),
-        "HOME" => Ok(Home),
-        "INSERT" => Ok(Insert),
-        "PAGEDOWN" => Ok(PageDown),
-        "PAGEUP" => Ok(PageUp),
-        "PRINTSCREEN" => Ok(PrintScreen),
-        "SCROLLLOCK" => Ok(ScrollLock),
-        "ARROWDOWN" | "DOWN" => Ok(ArrowDown),
-        "ARROWLEFT" | "LEFT" => Ok(ArrowLeft),
-        "ARROWRIGHT" | "RIGHT" => Ok(ArrowRight),
-        "ARROWUP" | "UP" => Ok(ArrowUp),
-        "NUMLOCK" => Ok(NumLock),
-        "NUMPAD0" | "NUM0" => Ok(Numpad0),
-        "NUMPAD1" | "NUM1" => Ok(Numpad1),
-        "NUMPAD2" | "NUM2" => Ok(Numpad2),
-        "NUMPAD3" | "NUM3" => Ok(Numpad3),
-        "NUMPAD4" | "NUM4" => Ok(Numpad4),
-        "NUMPAD5" | "NUM5" => Ok(Numpad5),
-        "NUMPAD6" | "NUM6" => Ok(Numpad6),
-        "NUMPAD7" | "NUM7" => Ok(Numpad7),
-        "NUMPAD8" | "NUM8" => Ok(Numpad8),
-        "NUMPAD9" | "NUM9" => Ok(Numpad9),
-        "NUMPADADD" | "NUMADD" | "NUMPADPLUS" | "NUMPLUS" => Ok(NumpadAdd),
-        "NUMPADDECIMAL" | "NUMDECIMAL" => Ok(NumpadDecimal),
-        "NUMPADDIVIDE" | "NUMDIVIDE" => Ok(NumpadDivide),
-        "NUMPADENTER" | "NUMENTER" => Ok(NumpadEnter),
-        "NUMPADEQUAL" | "NUMEQUAL" => Ok(NumpadEqual),
-        "NUMPADMULTIPLY" | "NUMMULTIPLY" => Ok(NumpadMultiply),
-        "NUMPADSUBTRACT" | "NUMSUBTRACT" => Ok(NumpadSubtract),
-        "ESCAPE" | "ESC" => Ok(Escape),
-        "F1" => Ok(F1),
-        "F2" => Ok(F2),
-        "F3" => Ok(F3),
-        "F4" => Ok(F4),
-        "F5" => Ok(F5),
-        "F6" => Ok(F6),
-        "F7" => Ok(F7),
-        "F8" => Ok(F8),
-        "F9" => Ok(F9),
-        "F10" => Ok(F10),
-        "F11" => Ok(F11),
-        "F12" => Ok(F12),
-        "AUDIOVOLUMEDOWN" | "VOLUMEDOWN" => Ok(AudioVolumeDown),
-        "AUDIOVOLUMEUP" | "VOLUMEUP" => Ok(AudioVolumeUp),
-        "AUDIOVOLUMEMUTE" | "VOLUMEMUTE" => Ok(AudioVolumeMute),
-        "F13" => Ok(F13),
-        "F14" => Ok(F14),
-        "F15" => Ok(F15),
-        "F16" => Ok(F16),
-        "F17" => Ok(F17),
-        "F18" => Ok(F18),
-        "F19" => Ok(F19),
-        "F20" => Ok(F20),
-        "F21" => Ok(F21),
-        "F22" => Ok(F22),
-        "F23" => Ok(F23),
-        "F24" => Ok(F24),
-
-        _ => Err(HotKeyParseError::UnsupportedKey(key.to_string())),
+/// Splits a hotkey on `+`, a `+` at the end after another `+` is the plus key itself,
+/// like in Electron's `Ctrl++`.
+fn tokenize(hotkey: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut rest = hotkey;
+    while let Some(i) = rest.find('+') {
+        let (token, after) = (&rest[..i], &rest[i + 1..]);
+        if token.trim().is_empty() && after.trim().is_empty() {
+            tokens.push(&rest[i..=i]);
+            return tokens;
+        }
+        tokens.push(token);
+        rest = after;
+    }
+    tokens.push(rest);
+    tokens
+}
+
+/// Parses the main key of a hotkey: a single character or `Char(<hex codepoint>)` is bound by character,
+/// a key name or `Code(<name>)` by physical position.
+fn parse_trigger(token: &str) -> Option<Trigger> {
+    let mut chars = token.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(Trigger::Character(c));
+    }
+
+    if let Some(codepoint) = token
+        .strip_prefix("Char(")
+        .or_else(|| token.strip_prefix("char("))
+        .and_then(|c| c.strip_suffix(')'))
+    {
+        let codepoint = codepoint
+            .strip_prefix("0x")
+            .or_else(|| codepoint.strip_prefix("0X"))
+            .unwrap_or(codepoint);
+        return u32::from_str_radix(codepoint, 16)
+            .ok()
+            .and_then(char::from_u32)
+            .map(Trigger::Character);
     }
+
+    if token.eq_ignore_ascii_case("Plus") {
+        return Some(Trigger::Character('+'));
+    }
+
+    if let Some(trigger) = parse_pointer(token) {
+        return Some(trigger);
+    }
+
+    let code = token
+        .strip_prefix("Code(")
+        .or_else(|| token.strip_prefix("code("))
+        .and_then(|code| code.strip_suffix(')'));
+    parse_key(code.unwrap_or(token)).map(Trigger::Key)
+}
+
+/// Parses a mouse button, like `Mouse4` or `MiddleClick`, or a wheel direction, like `WheelUp`.
+fn parse_pointer(token: &str) -> Option<Trigger> {
+    let token = token.to_uppercase();
+    Some(match token.as_str() {
+        "LEFTCLICK" | "LEFTBUTTON" => Trigger::Mouse(MouseButton::Left),
+        "RIGHTCLICK" | "RIGHTBUTTON" => Trigger::Mouse(MouseButton::Right),
+        "MIDDLECLICK" | "MIDDLEBUTTON" => Trigger::Mouse(MouseButton::Middle),
+        "BACKBUTTON" | "MOUSEBACK" => Trigger::Mouse(MouseButton::Back),
+        "FORWARDBUTTON" | "MOUSEFORWARD" => Trigger::Mouse(MouseButton::Forward),
+        "WHEELUP" | "SCROLLUP" => Trigger::Wheel(WheelDirection::Up),
+        "WHEELDOWN" | "SCROLLDOWN" => Trigger::Wheel(WheelDirection::Down),
+        "WHEELLEFT" | "SCROLLLEFT" => Trigger::Wheel(WheelDirection::Left),
+        "WHEELRIGHT" | "SCROLLRIGHT" => Trigger::Wheel(WheelDirection::Right),
+        _ => {
+            let number = token.strip_prefix("MOUSE")?.parse().ok()?;
+            Trigger::Mouse(MouseButton::from_number(number)?)
+        }
+    })
+}
+
+/// Parses a modifier token into its modifier and, when restricted to one key, its side.
+fn parse_modifier(token: &str) -> Option<(Modifiers, ModifierSides)> {
+    Some(match token.to_uppercase().as_str() {
+        "OPTION" | "ALT" => (Modifiers::ALT, ModifierSides::empty()),
+        "CONTROL" | "CTRL" => (Modifiers::CONTROL, ModifierSides::empty()),
+        "COMMAND" | "CMD" | "SUPER" => (Modifiers::SUPER, ModifierSides::empty()),
+        "SHIFT" => (Modifiers::SHIFT, ModifierSides::empty()),
+        "META" => (Modifiers::META, ModifierSides::empty()),
+        "HYPER" => (Modifiers::HYPER, ModifierSides::empty()),
+        "ALTGR" | "ALTGRAPH" => (Modifiers::ALT_GRAPH, ModifierSides::empty()),
+        "LSHIFT" | "LEFTSHIFT" | "SHIFTLEFT" => (Modifiers::empty(), ModifierSides::SHIFT_LEFT),
+        "RSHIFT" | "RIGHTSHIFT" | "SHIFTRIGHT" => (Modifiers::empty(), ModifierSides::SHIFT_RIGHT),
+        "LCONTROL" | "LCTRL" | "LEFTCONTROL" | "LEFTCTRL" | "CONTROLLEFT" => {
+            (Modifiers::empty(), ModifierSides::CONTROL_LEFT)
+        }
+        "RCONTROL" | "RCTRL" | "RIGHTCONTROL" | "RIGHTCTRL" | "CONTROLRIGHT" => {
+            (Modifiers::empty(), ModifierSides::CONTROL_RIGHT)
+        }
+        "LOPTION" | "LALT" | "LEFTOPTION" | "LEFTALT" | "ALTLEFT" => {
+            (Modifiers::empty(), ModifierSides::ALT_LEFT)
+        }
+        "ROPTION" | "RALT" | "RIGHTOPTION" | "RIGHTALT" | "ALTRIGHT" => {
+            (Modifiers::empty(), ModifierSides::ALT_RIGHT)
+        }
+        "LCOMMAND" | "LCMD" | "LSUPER" | "LEFTCOMMAND" | "LEFTCMD" | "LEFTSUPER" | "METALEFT" => {
+            (Modifiers::empty(), ModifierSides::SUPER_LEFT)
+        }
+        "RCOMMAND" | "RCMD" | "RSUPER" | "RIGHTCOMMAND" | "RIGHTCMD" | "RIGHTSUPER"
+        | "METARIGHT" => (Modifiers::empty(), ModifierSides::SUPER_RIGHT),
+        "COMMANDORCONTROL" | "COMMANDORCTRL" | "CMDORCTRL" | "CMDORCONTROL" => {
+            (CMD_OR_CTRL, ModifierSides::empty())
+        }
+        _ => return None,
+    })
+}
+
+/// Parses a key name, see [`KEY_NAMES`].
+fn parse_key(key: &str) -> Option<Code> {
+    use Code::*;
+    Some(match key.to_uppercase().as_str() {
+        "BACKQUOTE" | "`" => Backquote,
+        "BACKSLASH" | "\\" => Backslash,
+        "BRACKETLEFT" | "[" => BracketLeft,
+        "BRACKETRIGHT" | "]" => BracketRight,
+        "COMMA" | "," => Comma,
+        "DIGIT0" | "0" => Digit0,
+        "DIGIT1" | "1" => Digit1,
+        "DIGIT2" | "2" => Digit2,
+        "DIGIT3" | "3" => Digit3,
+        "DIGIT4" | "4" => Digit4,
+        "DIGIT5" | "5" => Digit5,
+        "DIGIT6" | "6" => Digit6,
+        "DIGIT7" | "7" => Digit7,
+        "DIGIT8" | "8" => Digit8,
+        "DIGIT9" | "9" => Digit9,
+        "EQUAL" | "=" => Equal,
+        "KEYA" | "A" => KeyA,
+        "KEYB" | "B" => KeyB,
+        "KEYC" | "C" => KeyC,
+        "KEYD" | "D" => KeyD,
+        "KEYE" | "E" => KeyE,
+        "KEYF" | "F" => KeyF,
+        "KEYG" | "G" => KeyG,
+        "KEYH" | "H" => KeyH,
+        "KEYI" | "I" => KeyI,
+        "KEYJ" | "J" => KeyJ,
+        "KEYK" | "K" => KeyK,
+        "KEYL" | "L" => KeyL,
+        "KEYM" | "M" => KeyM,
+        "KEYN" | "N" => KeyN,
+        "KEYO" | "O" => KeyO,
+        "KEYP" | "P" => KeyP,
+        "KEYQ" | "Q" => KeyQ,
+        "KEYR" | "R" => KeyR,
+        "KEYS" | "S" => KeyS,
+        "KEYT" | "T" => KeyT,
+        "KEYU" | "U" => KeyU,
+        "KEYV" | "V" => KeyV,
+        "KEYW" | "W" => KeyW,
+        "KEYX" | "X" => KeyX,
+        "KEYY" | "Y" => KeyY,
+        "KEYZ" | "Z" => KeyZ,
+        "MINUS" | "-" => Minus,
+        "PERIOD" | "." => Period,
+        "QUOTE" | "'" => Quote,
+        "SEMICOLON" | ";" => Semicolon,
+        "SLASH" | "/" => Slash,
+        "INTLBACKSLASH" => IntlBackslash,
+        "INTLRO" => IntlRo,
+        "INTLYEN" => IntlYen,
+        "BACKSPACE" => Backspace,
+        "CAPSLOCK" => CapsLock,
+        "ENTER" | "RETURN" => Enter,
+        "SPACE" => Space,
+        "TAB" => Tab,
+        "ALTLEFT" => AltLeft,
+        "ALTRIGHT" => AltRight,
+        "CONTEXTMENU" => ContextMenu,
+        "CONTROLLEFT" => ControlLeft,
+        "CONTROLRIGHT" => ControlRight,
+        "METALEFT" => MetaLeft,
+        "METARIGHT" => MetaRight,
+        "SHIFTLEFT" => ShiftLeft,
+        "SHIFTRIGHT" => ShiftRight,
+        "CONVERT" => Convert,
+        "KANAMODE" => KanaMode,
+        "LANG1" => Lang1,
+        "LANG2" => Lang2,
+        "LANG3" => Lang3,
+        "LANG4" => Lang4,
+        "LANG5" => Lang5,
+        "NONCONVERT" => NonConvert,
+        "DELETE" => Delete,
+        "END" => End,
+        "HOME" => Home,
+        "INSERT" => Insert,
+        "PAGEDOWN" => PageDown,
+        "PAGEUP" => PageUp,
+        "HELP" => Help,
+        "PRINTSCREEN" => PrintScreen,
+        "SCROLLLOCK" => ScrollLock,
+        "ARROWDOWN" | "DOWN" => ArrowDown,
+        "ARROWLEFT" | "LEFT" => ArrowLeft,
+        "ARROWRIGHT" | "RIGHT" => ArrowRight,
+        "ARROWUP" | "UP" => ArrowUp,
+        "NUMLOCK" => NumLock,
+        "NUMPAD0" | "NUM0" => Numpad0,
+        "NUMPAD1" | "NUM1" => Numpad1,
+        "NUMPAD2" | "NUM2" => Numpad2,
+        "NUMPAD3" | "NUM3" => Numpad3,
+        "NUMPAD4" | "NUM4" => Numpad4,
+        "NUMPAD5" | "NUM5" => Numpad5,
+        "NUMPAD6" | "NUM6" => Numpad6,
+        "NUMPAD7" | "NUM7" => Numpad7,
+        "NUMPAD8" | "NUM8" => Numpad8,
+        "NUMPAD9" | "NUM9" => Numpad9,
+        "NUMPADADD" | "NUMADD" | "NUMPADPLUS" | "NUMPLUS" => NumpadAdd,
+        "NUMPADDECIMAL" | "NUMDECIMAL" | "NUMDEC" => NumpadDecimal,
+        "NUMPADDIVIDE" | "NUMDIVIDE" | "NUMDIV" => NumpadDivide,
+        "NUMPADENTER" | "NUMENTER" => NumpadEnter,
+        "NUMPADEQUAL" | "NUMEQUAL" => NumpadEqual,
+        "NUMPADMULTIPLY" | "NUMMULTIPLY" | "NUMMULT" => NumpadMultiply,
+        "NUMPADSUBTRACT" | "NUMSUBTRACT" | "NUMSUB" => NumpadSubtract,
+        "NUMPADBACKSPACE" => NumpadBackspace,
+        "NUMPADCLEAR" => NumpadClear,
+        "NUMPADCLEARENTRY" => NumpadClearEntry,
+        "NUMPADCOMMA" => NumpadComma,
+        "NUMPADHASH" => NumpadHash,
+        "NUMPADMEMORYADD" => NumpadMemoryAdd,
+        "NUMPADMEMORYCLEAR" => NumpadMemoryClear,
+        "NUMPADMEMORYRECALL" => NumpadMemoryRecall,
+        "NUMPADMEMORYSTORE" => NumpadMemoryStore,
+        "NUMPADMEMORYSUBTRACT" => NumpadMemorySubtract,
+        "NUMPADPARENLEFT" => NumpadParenLeft,
+        "NUMPADPARENRIGHT" => NumpadParenRight,
+        "NUMPADSTAR" => NumpadStar,
+        "ESCAPE" | "ESC" => Escape,
+        "FN" => Fn,
+        "FNLOCK" => FnLock,
+        "PAUSE" => Pause,
+        "F1" => F1,
+        "F2" => F2,
+        "F3" => F3,
+        "F4" => F4,
+        "F5" => F5,
+        "F6" => F6,
+        "F7" => F7,
+        "F8" => F8,
+        "F9" => F9,
+        "F10" => F10,
+        "F11" => F11,
+        "F12" => F12,
+        "AUDIOVOLUMEDOWN" | "VOLUMEDOWN" => AudioVolumeDown,
+        "AUDIOVOLUMEUP" | "VOLUMEUP" => AudioVolumeUp,
+        "AUDIOVOLUMEMUTE" | "VOLUMEMUTE" => AudioVolumeMute,
+        "MEDIAPLAYPAUSE" => MediaPlayPause,
+        "MEDIASTOP" => MediaStop,
+        "MEDIATRACKNEXT" | "MEDIANEXTTRACK" => MediaTrackNext,
+        "MEDIATRACKPREVIOUS" | "MEDIAPREVIOUSTRACK" => MediaTrackPrevious,
+        "F13" => F13,
+        "F14" => F14,
+        "F15" => F15,
+        "F16" => F16,
+        "F17" => F17,
+        "F18" => F18,
+        "F19" => F19,
+        "F20" => F20,
+        "F21" => F21,
+        "F22" => F22,
+        "F23" => F23,
+        "F24" => F24,
+        "BROWSERBACK" => BrowserBack,
+        "BROWSERFAVORITES" => BrowserFavorites,
+        "BROWSERFORWARD" => BrowserForward,
+        "BROWSERHOME" => BrowserHome,
+        "BROWSERREFRESH" => BrowserRefresh,
+        "BROWSERSEARCH" => BrowserSearch,
+        "BROWSERSTOP" => BrowserStop,
+        "EJECT" => Eject,
+        "LAUNCHAPP1" => LaunchApp1,
+        "LAUNCHAPP2" => LaunchApp2,
+        "LAUNCHMAIL" => LaunchMail,
+        "MEDIASELECT" => MediaSelect,
+        "POWER" => Power,
+        "SLEEP" => Sleep,
+        "WAKEUP" => WakeUp,
+        "HYPER" => Hyper,
+        "SUPER" => Super,
+        "TURBO" => Turbo,
+        "ABORT" => Abort,
+        "RESUME" => Resume,
+        "SUSPEND" => Suspend,
+        "AGAIN" => Again,
+        "COPY" => Copy,
+        "CUT" => Cut,
+        "FIND" => Find,
+        "OPEN" => Open,
+        "PASTE" => Paste,
+        "PROPS" => Props,
+        "SELECT" => Select,
+        "UNDO" => Undo,
+        "HIRAGANA" => Hiragana,
+        "KATAKANA" => Katakana,
+        "F25" => F25,
+        "F26" => F26,
+        "F27" => F27,
+        "F28" => F28,
+        "F29" => F29,
+        "F30" => F30,
+        "F31" => F31,
+        "F32" => F32,
+        "F33" => F33,
+        "F34" => F34,
+        "F35" => F35,
+        "BRIGHTNESSDOWN" => BrightnessDown,
+        "BRIGHTNESSUP" => BrightnessUp,
+        "DISPLAYTOGGLEINTEXT" => DisplayToggleIntExt,
+        "KEYBOARDLAYOUTSELECT" => KeyboardLayoutSelect,
+        "LAUNCHASSISTANT" => LaunchAssistant,
+        "LAUNCHCONTROLPANEL" => LaunchControlPanel,
+        "LAUNCHSCREENSAVER" => LaunchScreenSaver,
+        "MAILFORWARD" => MailForward,
+        "MAILREPLY" => MailReply,
+        "MAILSEND" => MailSend,
+        "MEDIAFASTFORWARD" => MediaFastForward,
+        "MEDIAPAUSE" => MediaPause,
+        "MEDIAPLAY" => MediaPlay,
+        "MEDIARECORD" => MediaRecord,
+        "MEDIAREWIND" => MediaRewind,
+        "MICROPHONEMUTETOGGLE" => MicrophoneMuteToggle,
+        "PRIVACYSCREENTOGGLE" => PrivacyScreenToggle,
+        "SELECTTASK" => SelectTask,
+        "SHOWALLWINDOWS" => ShowAllWindows,
+        "ZOOMTOGGLE" => ZoomToggle,
+
+        _ => return None,
+    })
 }
 
+/// The key names accepted by [`parse_key`] and [`parse_trigger`], suggested when a key is misspelled.
+const KEY_NAMES: &[&str] = &[
+    "Backquote",
+    "Backslash",
+    "BracketLeft",
+    "BracketRight",
+    "Comma",
+    "Digit0",
+    "Digit1",
+    "Digit2",
+    "Digit3",
+    "Digit4",
+    "Digit5",
+    "Digit6",
+    "Digit7",
+    "Digit8",
+    "Digit9",
+    "Equal",
+    "KeyA",
+    "KeyB",
+    "KeyC",
+    "KeyD",
+    "KeyE",
+    "KeyF",
+    "KeyG",
+    "KeyH",
+    "KeyI",
+    "KeyJ",
+    "KeyK",
+    "KeyL",
+    "KeyM",
+    "KeyN",
+    "KeyO",
+    "KeyP",
+    "KeyQ",
+    "KeyR",
+    "KeyS",
+    "KeyT",
+    "KeyU",
+    "KeyV",
+    "KeyW",
+    "KeyX",
+    "KeyY",
+    "KeyZ",
+    "Minus",
+    "Period",
+    "Quote",
+    "Semicolon",
+    "Slash",
+    "IntlBackslash",
+    "IntlRo",
+    "IntlYen",
+    "Backspace",
+    "CapsLock",
+    "Enter",
+    "Return",
+    "Space",
+    "Tab",
+    "AltLeft",
+    "AltRight",
+    "ContextMenu",
+    "ControlLeft",
+    "ControlRight",
+    "MetaLeft",
+    "MetaRight",
+    "ShiftLeft",
+    "ShiftRight",
+    "Convert",
+    "KanaMode",
+    "Lang1",
+    "Lang2",
+    "Lang3",
+    "Lang4",
+    "Lang5",
+    "NonConvert",
+    "Delete",
+    "End",
+    "Home",
+    "Insert",
+    "PageDown",
+    "PageUp",
+    "Help",
+    "PrintScreen",
+    "ScrollLock",
+    "ArrowDown",
+    "Down",
+    "ArrowLeft",
+    "Left",
+    "ArrowRight",
+    "Right",
+    "ArrowUp",
+    "Up",
+    "NumLock",
+    "Numpad0",
+    "Num0",
+    "Numpad1",
+    "Num1",
+    "Numpad2",
+    "Num2",
+    "Numpad3",
+    "Num3",
+    "Numpad4",
+    "Num4",
+    "Numpad5",
+    "Num5",
+    "Numpad6",
+    "Num6",
+    "Numpad7",
+    "Num7",
+    "Numpad8",
+    "Num8",
+    "Numpad9",
+    "Num9",
+    "NumpadAdd",
+    "NumAdd",
+    "NumpadPlus",
+    "NumPlus",
+    "NumpadDecimal",
+    "NumDecimal",
+    "NumDec",
+    "NumpadDivide",
+    "NumDivide",
+    "NumDiv",
+    "NumpadEnter",
+    "NumEnter",
+    "NumpadEqual",
+    "NumEqual",
+    "NumpadMultiply",
+    "NumMultiply",
+    "NumMult",
+    "NumpadSubtract",
+    "NumSubtract",
+    "NumSub",
+    "NumpadBackspace",
+    "NumpadClear",
+    "NumpadClearEntry",
+    "NumpadComma",
+    "NumpadHash",
+    "NumpadMemoryAdd",
+    "NumpadMemoryClear",
+    "NumpadMemoryRecall",
+    "NumpadMemoryStore",
+    "NumpadMemorySubtract",
+    "NumpadParenLeft",
+    "NumpadParenRight",
+    "NumpadStar",
+    "Escape",
+    "Esc",
+    "Fn",
+    "FnLock",
+    "Pause",
+    "F1",
+    "F2",
+    "F3",
+    "F4",
+    "F5",
+    "F6",
+    "F7",
+    "F8",
+    "F9",
+    "F10",
+    "F11",
+    "F12",
+    "AudioVolumeDown",
+    "VolumeDown",
+    "AudioVolumeUp",
+    "VolumeUp",
+    "AudioVolumeMute",
+    "VolumeMute",
+    "MediaPlayPause",
+    "MediaStop",
+    "MediaTrackNext",
+    "MediaNextTrack",
+    "MediaTrackPrevious",
+    "MediaPreviousTrack",
+    "F13",
+    "F14",
+    "F15",
+    "F16",
+    "F17",
+    "F18",
+    "F19",
+    "F20",
+    "F21",
+    "F22",
+    "F23",
+    "F24",
+    "BrowserBack",
+    "BrowserFavorites",
+    "BrowserForward",
+    "BrowserHome",
+    "BrowserRefresh",
+    "BrowserSearch",
+    "BrowserStop",
+    "Eject",
+    "LaunchApp1",
+    "LaunchApp2",
+    "LaunchMail",
+    "MediaSelect",
+    "Power",
+    "Sleep",
+    "WakeUp",
+    "Hyper",
+    "Super",
+    "Turbo",
+    "Abort",
+    "Resume",
+    "Suspend",
+    "Again",
+    "Copy",
+    "Cut",
+    "Find",
+    "Open",
+    "Paste",
+    "Props",
+    "Select",
+    "Undo",
+    "Hiragana",
+    "Katakana",
+    "F25",
+    "F26",
+    "F27",
+    "F28",
+    "F29",
+    "F30",
+    "F31",
+    "F32",
+    "F33",
+    "F34",
+    "F35",
+    "BrightnessDown",
+    "BrightnessUp",
+    "DisplayToggleIntExt",
+    "KeyboardLayoutSelect",
+    "LaunchAssistant",
+    "LaunchControlPanel",
+    "LaunchScreenSaver",
+    "MailForward",
+    "MailReply",
+    "MailSend",
+    "MediaFastForward",
+    "MediaPause",
+    "MediaPlay",
+    "MediaRecord",
+    "MediaRewind",
+    "MicrophoneMuteToggle",
+    "PrivacyScreenToggle",
+    "SelectTask",
+    "ShowAllWindows",
+    "ZoomToggle",
+    "LeftClick",
+    "RightClick",
+    "MiddleClick",
+    "BackButton",
+    "ForwardButton",
+    "WheelUp",
+    "WheelDown",
+    "WheelLeft",
+    "WheelRight",
+    "ScrollUp",
+    "ScrollDown",
+    "ScrollLeft",
+    "ScrollRight",
+    "Plus",
+];
+
+/// The modifier names accepted by [`parse_modifier`], suggested when a modifier is misspelled.
+const MODIFIER_NAMES: &[&str] = &[
+    "Shift",
+    "Control",
+    "Ctrl",
+    "Alt",
+    "Option",
+    "Super",
+    "Command",
+    "Cmd",
+    "Meta",
+    "Hyper",
+    "AltGr",
+    "AltGraph",
+    "CapsLock",
+    "CmdOrCtrl",
+    "CmdOrControl",
+    "CommandOrCtrl",
+    "CommandOrControl",
+    "LShift",
+    "RShift",
+    "LeftShift",
+    "RightShift",
+    "ShiftLeft",
+    "ShiftRight",
+    "LCtrl",
+    "RCtrl",
+    "LControl",
+    "RControl",
+    "LeftCtrl",
+    "RightCtrl",
+    "LeftControl",
+    "RightControl",
+    "ControlLeft",
+    "ControlRight",
+    "LAlt",
+    "RAlt",
+    "LeftAlt",
+    "RightAlt",
+    "AltLeft",
+    "AltRight",
+    "LOption",
+    "ROption",
+    "LeftOption",
+    "RightOption",
+    "LSuper",
+    "RSuper",
+    "LeftSuper",
+    "RightSuper",
+    "LCmd",
+    "RCmd",
+    "LeftCmd",
+    "RightCmd",
+    "LCommand",
+    "RCommand",
+    "LeftCommand",
+    "RightCommand",
+    "MetaLeft",
+    "MetaRight",
+];
+
 #[test]
 fn test_parse_hotkey() {
     macro_rules! assert_parse_hotkey {
@@ -320,7 +1349,7 @@ fn test_parse_hotkey() {
             let r = parse_hotkey($key).unwrap();
             let l = $lrh;
             assert_eq!(r.mods, l.mods);
-            assert_eq!(r.key, l.key);
+            assert_eq!(r.trigger, l.trigger);
         };
     }
 
@@ -328,7 +1357,8 @@ fn test_parse_hotkey() {
         "KeyX",
         HotKey {
             mods: Modifiers::empty(),
-            key: Code::KeyX,
+            sides: ModifierSides::empty(),
+            trigger: Trigger::Key(Code::KeyX),
             id: 0,
         }
     );
@@ -337,7 +1367,8 @@ fn test_parse_hotkey() {
         "CTRL+KeyX",
         HotKey {
             mods: Modifiers::CONTROL,
-            key: Code::KeyX,
+            sides: ModifierSides::empty(),
+            trigger: Trigger::Key(Code::KeyX),
             id: 0,
         }
     );
@@ -346,7 +1377,8 @@ fn test_parse_hotkey() {
         "SHIFT+KeyC",
         HotKey {
             mods: Modifiers::SHIFT,
-            key: Code::KeyC,
+            sides: ModifierSides::empty(),
+            trigger: Trigger::Key(Code::KeyC),
             id: 0,
         }
     );
@@ -355,7 +1387,8 @@ fn test_parse_hotkey() {
         "SHIFT+KeyC",
         HotKey {
             mods: Modifiers::SHIFT,
-            key: Code::KeyC,
+            sides: ModifierSides::empty(),
+            trigger: Trigger::Key(Code::KeyC),
             id: 0,
         }
     );
@@ -364,7 +1397,8 @@ fn test_parse_hotkey() {
         "super+ctrl+SHIFT+alt+ArrowUp",
         HotKey {
             mods: Modifiers::SUPER | Modifiers::CONTROL | Modifiers::SHIFT | Modifiers::ALT,
-            key: Code::ArrowUp,
+            sides: ModifierSides::empty(),
+            trigger: Trigger::Key(Code::ArrowUp),
             id: 0,
         }
     );
@@ -372,7 +1406,8 @@ fn test_parse_hotkey() {
         "Digit5",
         HotKey {
             mods: Modifiers::empty(),
-            key: Code::Digit5,
+            sides: ModifierSides::empty(),
+            trigger: Trigger::Key(Code::Digit5),
             id: 0,
         }
     );
@@ -380,7 +1415,8 @@ fn test_parse_hotkey() {
         "KeyG",
         HotKey {
             mods: Modifiers::empty(),
-            key: Code::KeyG,
+            sides: ModifierSides::empty(),
+            trigger: Trigger::Key(Code::KeyG),
             id: 0,
         }
     );
@@ -389,7 +1425,8 @@ fn test_parse_hotkey() {
         "SHiFT+F12",
         HotKey {
             mods: Modifiers::SHIFT,
-            key: Code::F12,
+            sides: ModifierSides::empty(),
+            trigger: Trigger::Key(Code::F12),
             id: 0,
         }
     );
@@ -401,7 +1438,8 @@ fn test_parse_hotkey() {
             mods: Modifiers::SUPER,
             #[cfg(not(target_os = "macos"))]
             mods: Modifiers::CONTROL,
-            key: Code::Space,
+            sides: ModifierSides::empty(),
+            trigger: Trigger::Key(Code::Space),
             id: 0,
         }
     );
@@ -425,3 +1463,371 @@ fn test_equality() {
             && h5.id() != h6.id()
     );
 }
+
+#[test]
+fn test_modifier_sides() {
+    let right = parse_hotkey("RCtrl+Space").unwrap();
+    let left = parse_hotkey("LeftCtrl+Space").unwrap();
+    let either = parse_hotkey("Ctrl+Space").unwrap();
+
+    assert_eq!(right.mods, Modifiers::CONTROL);
+    assert_eq!(right.sides(), ModifierSides::CONTROL_RIGHT);
+    assert_eq!(
+        right,
+        HotKey::new(None, Code::Space).with_sides(ModifierSides::CONTROL_RIGHT)
+    );
+    assert!(right.id() != left.id() && left.id() != either.id());
+    assert_eq!(right.with_sides(ModifierSides::empty()), either);
+    assert_eq!(parse_hotkey("LCtrl+RCtrl+Space").unwrap().id(), either.id());
+
+    let pressed = ModifierSides::CONTROL_RIGHT | ModifierSides::SHIFT_LEFT;
+    assert!(right.matches_with_sides(Modifiers::CONTROL, pressed, Code::Space));
+    assert!(!left.matches_with_sides(Modifiers::CONTROL, pressed, Code::Space));
+    assert!(either.matches_with_sides(Modifiers::CONTROL, pressed, Code::Space));
+    assert!(left.matches(Modifiers::CONTROL, Code::Space));
+}
+
+#[test]
+fn test_modifiers_only() {
+    let hotkey = parse_hotkey("Ctrl+Shift").unwrap();
+    assert_eq!(hotkey.trigger(), Trigger::ModifiersOnly);
+    assert_eq!(hotkey.mods(), Modifiers::CONTROL | Modifiers::SHIFT);
+    assert_eq!(hotkey.key(), None);
+    assert_eq!(
+        hotkey,
+        HotKey::modifiers_only(Modifiers::CONTROL | Modifiers::SHIFT)
+    );
+
+    let hotkey = parse_hotkey("super").unwrap();
+    assert_eq!(hotkey, HotKey::modifiers_only(Modifiers::SUPER));
+    assert_ne!(hotkey.id(), parse_hotkey("super+KeyA").unwrap().id());
+
+    let hotkey = parse_hotkey("RCtrl").unwrap();
+    assert_eq!(hotkey.mods(), Modifiers::CONTROL);
+    assert_eq!(hotkey.sides(), ModifierSides::CONTROL_RIGHT);
+
+    assert!(parse_hotkey("Ctrl+").is_err());
+}
+
+#[test]
+fn test_character_trigger() {
+    let hotkey = parse_hotkey("Ctrl+z").unwrap();
+    assert_eq!(hotkey.trigger(), Trigger::Character('z'));
+    assert_eq!(hotkey.key(), None);
+    assert_eq!(hotkey, parse_hotkey("Ctrl+Z").unwrap());
+    assert_eq!(hotkey, HotKey::from_trigger(Some(Modifiers::CONTROL), 'Z'));
+
+    let physical = parse_hotkey("Ctrl+Code(KeyZ)").unwrap();
+    assert_eq!(physical.trigger(), Trigger::Key(Code::KeyZ));
+    assert_eq!(physical, parse_hotkey("Ctrl+KeyZ").unwrap());
+    assert_ne!(physical.id(), hotkey.id());
+    // characters match the key of their US layout position, like the keys they used to parse to
+    assert!(hotkey.matches(Modifiers::CONTROL, Code::KeyZ));
+    assert!(parse_hotkey("CmdOrCtrl+Q")
+        .unwrap()
+        .matches(CMD_OR_CTRL, Code::KeyQ));
+    assert!(!parse_hotkey("Alt+é")
+        .unwrap()
+        .matches(Modifiers::ALT, Code::KeyE));
+
+    assert_eq!(
+        parse_hotkey("Alt+é").unwrap().trigger(),
+        Trigger::Character('é')
+    );
+    assert_eq!(Trigger::fallback_key(','), Some(Code::Comma));
+    assert_eq!(Trigger::fallback_key('é'), None);
+}
+
+#[test]
+fn test_pointer_triggers() {
+    let hotkey = parse_hotkey("Ctrl+Mouse4").unwrap();
+    assert_eq!(hotkey.trigger(), Trigger::Mouse(MouseButton::Back));
+    assert_eq!(hotkey.mods(), Modifiers::CONTROL);
+    assert_eq!(hotkey.key(), None);
+
+    assert_eq!(
+        parse_hotkey("Alt+MiddleClick").unwrap(),
+        HotKey::from_trigger(Some(Modifiers::ALT), MouseButton::Middle)
+    );
+    assert_eq!(
+        parse_hotkey("alt+mouse3").unwrap(),
+        parse_hotkey("Alt+MiddleClick").unwrap()
+    );
+    assert_eq!(
+        parse_hotkey("Super+WheelUp").unwrap(),
+        HotKey::from_trigger(Some(Modifiers::SUPER), WheelDirection::Up)
+    );
+    assert_eq!(
+        parse_hotkey("Mouse8").unwrap().trigger(),
+        Trigger::Mouse(MouseButton::Other(8))
+    );
+    assert_eq!(
+        HotKey::from_trigger(None, MouseButton::Other(1)),
+        HotKey::from_trigger(None, MouseButton::Left)
+    );
+    assert_eq!(
+        HotKey::from_parsed(
+            Modifiers::empty(),
+            ModifierSides::empty(),
+            MouseButton::Other(5).into()
+        )
+        .trigger(),
+        Trigger::Mouse(MouseButton::Forward)
+    );
+    assert_ne!(
+        parse_hotkey("WheelUp").unwrap().id(),
+        parse_hotkey("WheelDown").unwrap().id()
+    );
+
+    assert!(parse_hotkey("Mouse0").is_err());
+    assert!(parse_hotkey("Mouse").is_err());
+}
+
+#[test]
+fn test_extra_modifiers() {
+    let hotkey = parse_hotkey("Hyper+KeyA").unwrap();
+    assert_eq!(hotkey.mods(), Modifiers::HYPER);
+
+    let meta = parse_hotkey("Meta+KeyA").unwrap();
+    assert_eq!(meta.mods(), Modifiers::META);
+    assert_eq!(meta, HotKey::new(Some(Modifiers::META), Code::KeyA));
+    assert_ne!(meta.id(), parse_hotkey("Super+KeyA").unwrap().id());
+    assert_ne!(meta.id(), hotkey.id());
+
+    let hotkey = parse_hotkey("AltGr+KeyE").unwrap();
+    assert_eq!(hotkey.mods(), Modifiers::ALT_GRAPH);
+    assert_eq!(hotkey, parse_hotkey("AltGraph+KeyE").unwrap());
+    assert!(hotkey.matches(Modifiers::ALT_GRAPH, Code::KeyE));
+    assert!(!hotkey.matches(Modifiers::ALT, Code::KeyE));
+
+    let hotkey = parse_hotkey("CapsLock+KeyJ").unwrap();
+    assert_eq!(hotkey.mods(), Modifiers::CAPS_LOCK);
+    assert!(hotkey.matches(Modifiers::CAPS_LOCK, Code::KeyJ));
+    assert!(!hotkey.matches(Modifiers::empty(), Code::KeyJ));
+    // CapsLock being on doesn't prevent other hotkeys from matching
+    assert!(parse_hotkey("KeyJ")
+        .unwrap()
+        .matches(Modifiers::CAPS_LOCK, Code::KeyJ));
+
+    // CapsLock is still a key when it comes last
+    let hotkey = parse_hotkey("Ctrl+CapsLock").unwrap();
+    assert_eq!(hotkey.trigger(), Trigger::Key(Code::CapsLock));
+    assert_eq!(hotkey.mods(), Modifiers::CONTROL);
+}
+
+#[test]
+fn test_display_round_trip() {
+    let modifiers = [
+        "",
+        "Shift+",
+        "Ctrl+Alt+",
+        "CmdOrCtrl+Shift+",
+        "Super+Meta+Hyper+AltGr+",
+        "RCtrl+LShift+",
+        "CapsLock+",
+        "Alt+CapsLock+",
+    ];
+    // every key name, in any case and wrapped in `Code(..)` for the keys
+    for modifiers in modifiers {
+        for &name in KEY_NAMES {
+            let mut keys = vec![name.to_string(), name.to_lowercase()];
+            if parse_key(name).is_some() {
+                keys.push(format!("Code({name})"));
+            }
+            for key in keys {
+                let hotkey = format!("{modifiers}{key}");
+                let hotkey = parse_hotkey(&hotkey).unwrap_or_else(|e| panic!("{hotkey}: {e}"));
+                let string = hotkey.to_string();
+                assert_eq!(parse_hotkey(&string).ok(), Some(hotkey), "{string}");
+                // the canonical form is stable
+                assert_eq!(parse_hotkey(&string).unwrap().to_string(), string);
+            }
+        }
+    }
+
+    for hotkey in [
+        "Shift+Control+KeyK",
+        "LShift+RAlt+Space",
+        "Control+a",
+        "Alt+Plus",
+        "Char(0x20)",
+        "Super+Mouse4",
+        "Control+WheelUp",
+        "Shift+Control",
+        "CapsLock+Shift",
+        "Meta+Hyper+AltGraph+F5",
+    ] {
+        assert_eq!(parse_hotkey(hotkey).unwrap().to_string(), hotkey);
+    }
+    assert_eq!(
+        HotKey::from_trigger(Some(Modifiers::CONTROL), '+').to_string(),
+        "Control+Plus"
+    );
+    assert_eq!(
+        parse_hotkey("alt+shift+KEYQ").unwrap().to_string(),
+        "Shift+Alt+KeyQ"
+    );
+}
+
+#[test]
+fn test_electron_accelerators() {
+    let ctrl = Some(Modifiers::CONTROL);
+    let table = [
+        (
+            "CommandOrControl+Plus",
+            HotKey::from_trigger(Some(CMD_OR_CTRL), '+'),
+        ),
+        ("Ctrl++", HotKey::from_trigger(ctrl, '+')),
+        ("Ctrl + +", HotKey::from_trigger(ctrl, '+')),
+        ("+", HotKey::from_trigger(None, '+')),
+        ("Plus", HotKey::from_trigger(None, '+')),
+        ("Ctrl+-", HotKey::from_trigger(ctrl, '-')),
+        (
+            "CmdOrCtrl+Shift+Z",
+            HotKey::from_trigger(Some(CMD_OR_CTRL | Modifiers::SHIFT), 'z'),
+        ),
+        ("Alt+F4", HotKey::new(Some(Modifiers::ALT), Code::F4)),
+        (
+            "Option+Space",
+            HotKey::new(Some(Modifiers::ALT), Code::Space),
+        ),
+        (
+            "AltGr+E",
+            HotKey::from_trigger(Some(Modifiers::ALT_GRAPH), 'e'),
+        ),
+        ("Super+Tab", HotKey::new(Some(Modifiers::SUPER), Code::Tab)),
+        (
+            "Command+Q",
+            HotKey::from_trigger(Some(Modifiers::SUPER), 'q'),
+        ),
+        ("Super", HotKey::modifiers_only(Modifiers::SUPER)),
+        (
+            "Shift+Return",
+            HotKey::new(Some(Modifiers::SHIFT), Code::Enter),
+        ),
+        ("Ctrl+Esc", HotKey::new(ctrl, Code::Escape)),
+        ("Capslock", HotKey::new(None, Code::CapsLock)),
+        ("Numlock", HotKey::new(None, Code::NumLock)),
+        ("Scrolllock", HotKey::new(None, Code::ScrollLock)),
+        ("Ctrl+PageUp", HotKey::new(ctrl, Code::PageUp)),
+        ("PrintScreen", HotKey::new(None, Code::PrintScreen)),
+        ("VolumeUp", HotKey::new(None, Code::AudioVolumeUp)),
+        ("VolumeDown", HotKey::new(None, Code::AudioVolumeDown)),
+        ("VolumeMute", HotKey::new(None, Code::AudioVolumeMute)),
+        ("MediaPlayPause", HotKey::new(None, Code::MediaPlayPause)),
+        ("MediaStop", HotKey::new(None, Code::MediaStop)),
+        ("MediaNextTrack", HotKey::new(None, Code::MediaTrackNext)),
+        (
+            "MediaPreviousTrack",
+            HotKey::new(None, Code::MediaTrackPrevious),
+        ),
+        ("num0", HotKey::new(None, Code::Numpad0)),
+        ("Ctrl+num9", HotKey::new(ctrl, Code::Numpad9)),
+        ("numdec", HotKey::new(None, Code::NumpadDecimal)),
+        ("numadd", HotKey::new(None, Code::NumpadAdd)),
+        ("numsub", HotKey::new(None, Code::NumpadSubtract)),
+        ("nummult", HotKey::new(None, Code::NumpadMultiply)),
+        ("numdiv", HotKey::new(None, Code::NumpadDivide)),
+        ("F24", HotKey::new(None, Code::F24)),
+        ("Shift+~", HotKey::from_trigger(Some(Modifiers::SHIFT), '~')),
+        ("Ctrl+!", HotKey::from_trigger(ctrl, '!')),
+    ];
+    for (accelerator, expected) in table {
+        assert_eq!(
+            parse_hotkey(accelerator).ok(),
+            Some(expected),
+            "{accelerator}"
+        );
+    }
+
+    assert_eq!(
+        parse_hotkey("Ctrl+").unwrap_err().kind(),
+        HotKeyParseErrorKind::EmptyToken
+    );
+    assert!(parse_hotkey("Ctrl+++").is_err());
+    assert!(parse_hotkey("++Ctrl").is_err());
+}
+
+#[test]
+fn test_const_hotkeys() {
+    const SAVE: HotKey = HotKey::new(Some(Modifiers::CONTROL), Code::KeyS);
+    const SIDED: HotKey = HotKey::new(None, Code::KeyS).with_sides(ModifierSides::CONTROL_LEFT);
+    const SHIFT: HotKey = HotKey::modifiers_only(Modifiers::SHIFT);
+    const SAVE_ID: u32 = SAVE.id();
+
+    assert_eq!(SAVE, parse_hotkey("Ctrl+KeyS").unwrap());
+    assert_eq!(SAVE_ID, parse_hotkey("Control+KeyS").unwrap().id());
+    assert_eq!(SIDED, parse_hotkey("LCtrl+KeyS").unwrap());
+    assert_eq!(SHIFT, parse_hotkey("Shift").unwrap());
+    assert_ne!(SAVE.id(), SIDED.id());
+    assert_ne!(SAVE.id(), parse_hotkey("Ctrl+s").unwrap().id());
+}
+
+#[test]
+fn test_full_code_set() {
+    for (key, code) in [
+        ("MediaPlayPause", Code::MediaPlayPause),
+        ("BrowserBack", Code::BrowserBack),
+        ("LaunchApp1", Code::LaunchApp1),
+        ("IntlBackslash", Code::IntlBackslash),
+        ("intlyen", Code::IntlYen),
+        ("ContextMenu", Code::ContextMenu),
+        ("Pause", Code::Pause),
+        ("Power", Code::Power),
+        ("Sleep", Code::Sleep),
+        ("F35", Code::F35),
+        ("Code(ShiftLeft)", Code::ShiftLeft),
+        ("Code(Super)", Code::Super),
+    ] {
+        let hotkey = parse_hotkey(&format!("Ctrl+{key}")).unwrap();
+        assert_eq!(hotkey, HotKey::new(Some(Modifiers::CONTROL), code), "{key}");
+    }
+    assert!(parse_hotkey("Unidentified").is_err());
+
+    // keys named like a modifier are written as a `Code`
+    let hotkey = HotKey::new(Some(Modifiers::CONTROL), Code::ShiftLeft);
+    assert_eq!(hotkey.to_string(), "Control+Code(ShiftLeft)");
+    assert_eq!(parse_hotkey(&hotkey.to_string()).unwrap(), hotkey);
+}
+
+#[test]
+fn test_parse_diagnostics() {
+    use HotKeyParseErrorKind::*;
+
+    let error = parse_hotkey("Ctrl+Shft+K").unwrap_err();
+    assert_eq!(error.kind(), UnsupportedModifier);
+    assert_eq!(error.span(), 5..9);
+    assert_eq!(error.suggestions().first(), Some(&"Shift"));
+
+    let error = parse_hotkey("Ctrl + Escpe").unwrap_err();
+    assert_eq!(error.kind(), UnsupportedKey);
+    assert_eq!(error.token(), "Escpe");
+    assert_eq!(error.span(), 7..12);
+    assert_eq!(error.suggestions(), ["Escape"]);
+
+    let error = parse_hotkey("Ctrl+Shfit").unwrap_err();
+    assert_eq!(error.suggestions().first(), Some(&"Shift"));
+
+    let error = parse_hotkey("Ctrl+Xylophone").unwrap_err();
+    assert!(error.suggestions().is_empty());
+    assert!(error.to_string().contains("tauri-apps/global-hotkey"));
+
+    let error = parse_hotkey("Ctrl+A+B").unwrap_err();
+    assert_eq!((error.kind(), error.span()), (InvalidFormat, 7..8));
+
+    let error = parse_hotkey("Ctrl+ +A").unwrap_err();
+    assert_eq!((error.kind(), error.span().start), (EmptyToken, 5));
+
+    let error = parse_hotkey("").unwrap_err();
+    assert_eq!((error.kind(), error.span()), (EmptyToken, 0..0));
+
+    // every suggested name parses
+    for name in KEY_NAMES {
+        assert!(parse_trigger(name).is_some(), "{name}");
+    }
+    for name in MODIFIER_NAMES {
+        // CapsLock is only a modifier before the main key
+        let modifiers_only = parse_hotkey(&format!("{name}+Shift")).unwrap();
+        assert!(modifiers_only.trigger == Trigger::ModifiersOnly, "{name}");
+    }
+}
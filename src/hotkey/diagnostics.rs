@@ -0,0 +1,223 @@
+// Copyright 2022-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::ops::Range;
+
+/// What went wrong while parsing a hotkey, see [`HotKeyParseError::kind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum HotKeyParseErrorKind {
+    /// The main key isn't a known key, character or mouse button, like `Foo` in `Ctrl+Foo`.
+    UnsupportedKey,
+    /// A token before the main key isn't a modifier, like `Shft` in `Ctrl+Shft+K`.
+    UnsupportedModifier,
+    /// A token is empty, like the one after the `+` in `Ctrl+`.
+    EmptyToken,
+    /// The hotkey has more than one main key, or modifiers after the main key, like `C` in `Ctrl+A+C`.
+    InvalidFormat,
+}
+
+/// An error returned when a hotkey or a hotkey sequence can't be parsed.
+///
+/// It locates the offending token in the input, so it can be underlined in a settings screen,
+/// and suggests the closest names when a key or a modifier looks misspelled.
+///
+/// # Examples
+///
+/// ```
+/// # use global_hotkey::hotkey::{HotKey, HotKeyParseErrorKind};
+/// let error = "Ctrl+Shft+K".parse::<HotKey>().unwrap_err();
+/// assert_eq!(error.kind(), HotKeyParseErrorKind::UnsupportedModifier);
+/// assert_eq!(error.span(), 5..9);
+/// assert_eq!(error.token(), "Shft");
+/// assert_eq!(error.suggestions()[0], "Shift");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HotKeyParseError {
+    kind: HotKeyParseErrorKind,
+    input: String,
+    span: Range<usize>,
+    suggestions: Vec<&'static str>,
+}
+
+impl HotKeyParseError {
+    /// Creates an error about `token`, which must be a slice of `input`.
+    pub(crate) fn new(kind: HotKeyParseErrorKind, input: &str, token: &str) -> Self {
+        let start = offset_in(input, token);
+        Self {
+            kind,
+            input: input.to_string(),
+            span: start..start + token.len(),
+            suggestions: Vec::new(),
+        }
+    }
+
+    /// Suggests the names closest to the offending token, best first.
+    pub(crate) fn suggest(mut self, names: impl IntoIterator<Item = &'static str>) -> Self {
+        let token = self.token().to_lowercase();
+        let token_len = token.chars().count();
+        let mut ranked = names
+            .into_iter()
+            .map(|name| (edit_distance(&token, &name.to_lowercase()), name))
+            .filter(|(distance, name)| {
+                *distance <= (token_len.max(name.chars().count()) / 3).max(1)
+            })
+            .collect::<Vec<_>>();
+        // the sort is stable, so names listed first win ties
+        ranked.sort_by_key(|(distance, _)| *distance);
+        ranked.dedup_by(|(_, a), (_, b)| a.eq_ignore_ascii_case(b));
+        self.suggestions = ranked
+            .into_iter()
+            .map(|(_, name)| name)
+            .take(MAX_SUGGESTIONS)
+            .collect();
+        self
+    }
+
+    /// Moves this error, returned for `part`, into the larger `input` that `part` is a slice of.
+    pub(crate) fn within(mut self, input: &str, part: &str) -> Self {
+        let start = offset_in(input, part);
+        self.span = start + self.span.start..start + self.span.end;
+        self.input = input.to_string();
+        self
+    }
+
+    /// Returns what went wrong.
+    pub fn kind(&self) -> HotKeyParseErrorKind {
+        self.kind
+    }
+
+    /// Returns the string that was parsed.
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// Returns the byte range of the offending token in [`input`](Self::input).
+    ///
+    /// The range is empty for [`HotKeyParseErrorKind::EmptyToken`], it points where the token is missing.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// Returns the offending token.
+    pub fn token(&self) -> &str {
+        &self.input[self.span.clone()]
+    }
+
+    /// Returns the names closest to the offending token, best first.
+    ///
+    /// It is empty when nothing comes close or when the error isn't about a key or modifier name.
+    pub fn suggestions(&self) -> &[&'static str] {
+        &self.suggestions
+    }
+}
+
+impl std::fmt::Display for HotKeyParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (input, token) = (&self.input, self.token());
+        match self.kind {
+            HotKeyParseErrorKind::UnsupportedKey => {
+                write!(f, "Couldn't recognize \"{token}\" as a key in hotkey \"{input}\"")?
+            }
+            HotKeyParseErrorKind::UnsupportedModifier => {
+                write!(f, "Couldn't recognize \"{token}\" as a modifier in hotkey \"{input}\"")?
+            }
+            HotKeyParseErrorKind::EmptyToken => write!(
+                f,
+                "Found empty token at byte {} while parsing hotkey: \"{input}\"",
+                self.span.start
+            )?,
+            HotKeyParseErrorKind::InvalidFormat => write!(
+                f,
+                "Invalid hotkey format: \"{input}\" at \"{token}\", an hotkey should have the modifiers first and only one main key, for example: \"Shift + Alt + K\""
+            )?,
+        }
+        match self.suggestions.first() {
+            Some(suggestion) => write!(f, ", did you mean \"{suggestion}\"?"),
+            None if self.kind == HotKeyParseErrorKind::UnsupportedKey => write!(
+                f,
+                ", if you feel like it should be supported, please report this to https://github.com/tauri-apps/global-hotkey"
+            ),
+            None => Ok(()),
+        }
+    }
+}
+
+impl std::error::Error for HotKeyParseError {}
+
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Returns the byte offset of `part`, a slice of `input`, in `input`.
+fn offset_in(input: &str, part: &str) -> usize {
+    let offset = (part.as_ptr() as usize).wrapping_sub(input.as_ptr() as usize);
+    debug_assert!(
+        offset + part.len() <= input.len(),
+        "not a slice of the input"
+    );
+    offset.min(input.len())
+}
+
+/// Returns the edit distance between `a` and `b`, counted in characters, where inserting,
+/// deleting or replacing a character, or swapping two adjacent ones, is one edit.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let (a, b) = (a.chars().collect::<Vec<_>>(), b.chars().collect::<Vec<_>>());
+    // distances[i][j] is the distance between the first i characters of a and the first j of b
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = (distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1)
+                .min(distances[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+            distances[i][j] = distance;
+        }
+    }
+    distances[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edit_distances() {
+        assert_eq!(edit_distance("shft", "shift"), 1);
+        assert_eq!(edit_distance("ctlr", "ctrl"), 1);
+        assert_eq!(edit_distance("", "alt"), 3);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("é", "e"), 1);
+    }
+
+    #[test]
+    fn ranked_suggestions() {
+        let input = "Ctrl+Shft+K";
+        let error = HotKeyParseError::new(
+            HotKeyParseErrorKind::UnsupportedModifier,
+            input,
+            &input[5..9],
+        )
+        .suggest(["Alt", "Super", "Shift", "Shift"]);
+        assert_eq!(error.suggestions(), ["Shift"]);
+        assert!(error.to_string().ends_with(", did you mean \"Shift\"?"));
+
+        let error =
+            HotKeyParseError::new(HotKeyParseErrorKind::UnsupportedKey, input, &input[10..])
+                .suggest(["Escape"]);
+        assert!(error.suggestions().is_empty());
+
+        let sequence = format!("Ctrl+K {input}");
+        let error = error.within(&sequence, &sequence[7..]);
+        assert_eq!(error.span(), 17..18);
+        assert_eq!(error.token(), "K");
+    }
+}
@@ -0,0 +1,362 @@
+// Copyright 2022-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use keyboard_types::{Code, Modifiers};
+
+use super::{HotKey, HotKeyParseError, HotKeyParseErrorKind, Trigger, CMD_OR_CTRL};
+
+/// GTK keysym names of the keys, the first name of a key is the one written by
+/// [`HotKey::to_gtk_accelerator`].
+const KEYSYMS: &[(&str, Code)] = &[
+    ("a", Code::KeyA),
+    ("b", Code::KeyB),
+    ("c", Code::KeyC),
+    ("d", Code::KeyD),
+    ("e", Code::KeyE),
+    ("f", Code::KeyF),
+    ("g", Code::KeyG),
+    ("h", Code::KeyH),
+    ("i", Code::KeyI),
+    ("j", Code::KeyJ),
+    ("k", Code::KeyK),
+    ("l", Code::KeyL),
+    ("m", Code::KeyM),
+    ("n", Code::KeyN),
+    ("o", Code::KeyO),
+    ("p", Code::KeyP),
+    ("q", Code::KeyQ),
+    ("r", Code::KeyR),
+    ("s", Code::KeyS),
+    ("t", Code::KeyT),
+    ("u", Code::KeyU),
+    ("v", Code::KeyV),
+    ("w", Code::KeyW),
+    ("x", Code::KeyX),
+    ("y", Code::KeyY),
+    ("z", Code::KeyZ),
+    ("0", Code::Digit0),
+    ("1", Code::Digit1),
+    ("2", Code::Digit2),
+    ("3", Code::Digit3),
+    ("4", Code::Digit4),
+    ("5", Code::Digit5),
+    ("6", Code::Digit6),
+    ("7", Code::Digit7),
+    ("8", Code::Digit8),
+    ("9", Code::Digit9),
+    ("grave", Code::Backquote),
+    ("minus", Code::Minus),
+    ("equal", Code::Equal),
+    ("bracketleft", Code::BracketLeft),
+    ("bracketright", Code::BracketRight),
+    ("backslash", Code::Backslash),
+    ("semicolon", Code::Semicolon),
+    ("apostrophe", Code::Quote),
+    ("comma", Code::Comma),
+    ("period", Code::Period),
+    ("slash", Code::Slash),
+    ("space", Code::Space),
+    ("Return", Code::Enter),
+    ("Tab", Code::Tab),
+    ("BackSpace", Code::Backspace),
+    ("Escape", Code::Escape),
+    ("Delete", Code::Delete),
+    ("Insert", Code::Insert),
+    ("Home", Code::Home),
+    ("End", Code::End),
+    ("Page_Up", Code::PageUp),
+    ("Prior", Code::PageUp),
+    ("Page_Down", Code::PageDown),
+    ("Next", Code::PageDown),
+    ("Left", Code::ArrowLeft),
+    ("Right", Code::ArrowRight),
+    ("Up", Code::ArrowUp),
+    ("Down", Code::ArrowDown),
+    ("Print", Code::PrintScreen),
+    ("Scroll_Lock", Code::ScrollLock),
+    ("Num_Lock", Code::NumLock),
+    ("Caps_Lock", Code::CapsLock),
+    ("Pause", Code::Pause),
+    ("Menu", Code::ContextMenu),
+    ("F1", Code::F1),
+    ("F2", Code::F2),
+    ("F3", Code::F3),
+    ("F4", Code::F4),
+    ("F5", Code::F5),
+    ("F6", Code::F6),
+    ("F7", Code::F7),
+    ("F8", Code::F8),
+    ("F9", Code::F9),
+    ("F10", Code::F10),
+    ("F11", Code::F11),
+    ("F12", Code::F12),
+    ("F13", Code::F13),
+    ("F14", Code::F14),
+    ("F15", Code::F15),
+    ("F16", Code::F16),
+    ("F17", Code::F17),
+    ("F18", Code::F18),
+    ("F19", Code::F19),
+    ("F20", Code::F20),
+    ("F21", Code::F21),
+    ("F22", Code::F22),
+    ("F23", Code::F23),
+    ("F24", Code::F24),
+    ("KP_0", Code::Numpad0),
+    ("KP_1", Code::Numpad1),
+    ("KP_2", Code::Numpad2),
+    ("KP_3", Code::Numpad3),
+    ("KP_4", Code::Numpad4),
+    ("KP_5", Code::Numpad5),
+    ("KP_6", Code::Numpad6),
+    ("KP_7", Code::Numpad7),
+    ("KP_8", Code::Numpad8),
+    ("KP_9", Code::Numpad9),
+    ("KP_Add", Code::NumpadAdd),
+    ("KP_Subtract", Code::NumpadSubtract),
+    ("KP_Multiply", Code::NumpadMultiply),
+    ("KP_Divide", Code::NumpadDivide),
+    ("KP_Decimal", Code::NumpadDecimal),
+    ("KP_Enter", Code::NumpadEnter),
+    ("KP_Equal", Code::NumpadEqual),
+    ("XF86AudioRaiseVolume", Code::AudioVolumeUp),
+    ("XF86AudioLowerVolume", Code::AudioVolumeDown),
+    ("XF86AudioMute", Code::AudioVolumeMute),
+    ("XF86AudioPlay", Code::MediaPlayPause),
+    ("XF86AudioStop", Code::MediaStop),
+    ("XF86AudioNext", Code::MediaTrackNext),
+    ("XF86AudioPrev", Code::MediaTrackPrevious),
+];
+
+/// GTK modifier names, the first name of a modifier is the one written by
+/// [`HotKey::to_gtk_accelerator`], in the order GTK writes them.
+const MODIFIERS: &[(&str, Modifiers)] = &[
+    ("Shift", Modifiers::SHIFT),
+    ("Control", Modifiers::CONTROL),
+    ("Ctrl", Modifiers::CONTROL),
+    ("Ctl", Modifiers::CONTROL),
+    ("Alt", Modifiers::ALT),
+    ("Mod1", Modifiers::ALT),
+    ("Meta", Modifiers::META),
+    ("Super", Modifiers::SUPER),
+    ("Hyper", Modifiers::HYPER),
+];
+
+impl HotKey {
+    /// Parses a GTK accelerator, as used by GTK menus and GSettings keybindings,
+    /// like `<Primary><Alt>Delete` or `<Shift>Page_Up`.
+    ///
+    /// `<Primary>` stands for [`CMD_OR_CTRL`] and letters are bound by physical position,
+    /// like [`Code::KeyQ`] for `q` or `Q`. Keysyms of other characters, like `plus` or `U20AC`,
+    /// are bound by [`Trigger::Character`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use global_hotkey::hotkey::{HotKey, Modifiers, Code, CMD_OR_CTRL};
+    /// let hotkey = HotKey::from_gtk_accelerator("<Primary><Alt>Delete").unwrap();
+    /// assert_eq!(hotkey, HotKey::new(Some(CMD_OR_CTRL | Modifiers::ALT), Code::Delete));
+    /// assert_eq!(hotkey.to_gtk_accelerator().unwrap(), "<Control><Alt>Delete");
+    /// ```
+    pub fn from_gtk_accelerator(accelerator: &str) -> Result<Self, HotKeyParseError> {
+        use HotKeyParseErrorKind::*;
+
+        let mut mods = Modifiers::empty();
+        let mut rest = accelerator.trim();
+        while let Some(modifier) = rest.strip_prefix('<') {
+            let (name, after) = modifier
+                .split_once('>')
+                .ok_or_else(|| HotKeyParseError::new(InvalidFormat, accelerator, rest))?;
+            mods |= if name.eq_ignore_ascii_case("Primary") {
+                CMD_OR_CTRL
+            } else {
+                MODIFIERS
+                    .iter()
+                    .find(|(n, _)| n.eq_ignore_ascii_case(name))
+                    .map(|(_, m)| *m)
+                    .ok_or_else(|| {
+                        HotKeyParseError::new(UnsupportedModifier, accelerator, name)
+                            .suggest(MODIFIERS.iter().map(|(n, _)| *n).chain(["Primary"]))
+                    })?
+            };
+            rest = after;
+        }
+
+        if rest.is_empty() {
+            return Err(HotKeyParseError::new(EmptyToken, accelerator, rest));
+        }
+        let trigger = keysym_to_trigger(rest).ok_or_else(|| {
+            let keysyms = KEYSYMS.iter().map(|(n, _)| *n);
+            HotKeyParseError::new(UnsupportedKey, accelerator, rest)
+                .suggest(keysyms.chain(CHARACTER_KEYSYMS.iter().map(|(n, _)| *n)))
+        })?;
+        Ok(HotKey::from_trigger(Some(mods), trigger))
+    }
+
+    /// Writes this hotkey as a GTK accelerator, like `<Control><Alt>Delete`.
+    ///
+    /// Returns `None` if GTK can't describe this hotkey, for example when it is restricted
+    /// to a side of a modifier, has modifiers only or is bound to a mouse button.
+    pub fn to_gtk_accelerator(&self) -> Option<String> {
+        if !self.sides.is_empty() {
+            return None;
+        }
+
+        let mut accelerator = String::new();
+        let mut mods = self.mods;
+        for (name, modifier) in MODIFIERS {
+            if mods.contains(*modifier) {
+                accelerator.push_str(&format!("<{name}>"));
+                mods.remove(*modifier);
+            }
+        }
+        if !mods.is_empty() {
+            return None;
+        }
+
+        match self.trigger {
+            Trigger::Key(key) => {
+                let (name, _) = KEYSYMS.iter().find(|(_, code)| *code == key)?;
+                accelerator.push_str(name);
+            }
+            Trigger::Character(c) => accelerator.push_str(&char_to_keysym(c)),
+            _ => return None,
+        }
+        Some(accelerator)
+    }
+}
+
+fn keysym_to_trigger(keysym: &str) -> Option<Trigger> {
+    if let Some((_, code)) = KEYSYMS
+        .iter()
+        .find(|(name, _)| *name == keysym || (name.len() == 1 && name.eq_ignore_ascii_case(keysym)))
+    {
+        return Some(Trigger::Key(*code));
+    }
+
+    let c = match CHARACTER_KEYSYMS.iter().find(|(name, _)| *name == keysym) {
+        Some((_, c)) => *c,
+        // Unicode keysyms are written as `U` followed by the hex codepoint
+        None => keysym
+            .strip_prefix('U')
+            .and_then(|codepoint| u32::from_str_radix(codepoint, 16).ok())
+            .and_then(char::from_u32)?,
+    };
+    Some(Trigger::Character(c))
+}
+
+fn char_to_keysym(c: char) -> String {
+    match CHARACTER_KEYSYMS
+        .iter()
+        .find(|(_, character)| *character == c)
+    {
+        Some((name, _)) => name.to_string(),
+        None if c.is_ascii_alphanumeric() => c.to_string(),
+        None => format!("U{:04X}", c as u32),
+    }
+}
+
+/// GTK keysym names of the ASCII characters that aren't on a key of their own on a US keyboard.
+const CHARACTER_KEYSYMS: &[(&str, char)] = &[
+    ("exclam", '!'),
+    ("quotedbl", '"'),
+    ("numbersign", '#'),
+    ("dollar", '$'),
+    ("percent", '%'),
+    ("ampersand", '&'),
+    ("parenleft", '('),
+    ("parenright", ')'),
+    ("asterisk", '*'),
+    ("plus", '+'),
+    ("colon", ':'),
+    ("less", '<'),
+    ("greater", '>'),
+    ("question", '?'),
+    ("at", '@'),
+    ("asciicircum", '^'),
+    ("underscore", '_'),
+    ("braceleft", '{'),
+    ("bar", '|'),
+    ("braceright", '}'),
+    ("asciitilde", '~'),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hotkey::{ModifierSides, MouseButton};
+
+    #[test]
+    fn parse_gtk_accelerators() {
+        let parse = |s| HotKey::from_gtk_accelerator(s).unwrap();
+
+        assert_eq!(
+            parse("<Control><Shift>q"),
+            HotKey::new(Some(Modifiers::CONTROL | Modifiers::SHIFT), Code::KeyQ)
+        );
+        assert_eq!(parse("<Ctrl><Shift>Q"), parse("<Control><Shift>q"));
+        assert_eq!(
+            parse("<Primary>Page_Up"),
+            HotKey::new(Some(CMD_OR_CTRL), Code::PageUp)
+        );
+        assert_eq!(
+            parse("<Super><Hyper>KP_Add"),
+            HotKey::new(Some(Modifiers::SUPER | Modifiers::HYPER), Code::NumpadAdd)
+        );
+        assert_eq!(
+            parse("<Mod1>Prior"),
+            HotKey::new(Some(Modifiers::ALT), Code::PageUp)
+        );
+        assert_eq!(
+            parse("<Control>plus"),
+            HotKey::from_trigger(Some(Modifiers::CONTROL), '+')
+        );
+        assert_eq!(parse("U20AC"), HotKey::from_trigger(None, '€'));
+
+        assert!(HotKey::from_gtk_accelerator("<Control>").is_err());
+        assert!(HotKey::from_gtk_accelerator("<Control").is_err());
+        let error = HotKey::from_gtk_accelerator("<Foo>a").unwrap_err();
+        assert_eq!(error.kind(), HotKeyParseErrorKind::UnsupportedModifier);
+        assert_eq!(error.span(), 1..4);
+        let error = HotKey::from_gtk_accelerator("<Control>Page_Upp").unwrap_err();
+        assert_eq!(error.suggestions().first(), Some(&"Page_Up"));
+        assert!(HotKey::from_gtk_accelerator("<Control>Foo").is_err());
+    }
+
+    #[test]
+    fn write_gtk_accelerators() {
+        for accelerator in [
+            "<Shift><Control>q",
+            "<Alt>Delete",
+            "<Meta><Super><Hyper>F5",
+            "<Control>KP_Add",
+            "<Control>plus",
+            "<Super>U20AC",
+            "Return",
+        ] {
+            let hotkey = HotKey::from_gtk_accelerator(accelerator).unwrap();
+            assert_eq!(hotkey.to_gtk_accelerator().unwrap(), accelerator);
+        }
+
+        for &(_, code) in KEYSYMS {
+            let hotkey = HotKey::new(Some(Modifiers::CONTROL), code);
+            let accelerator = hotkey.to_gtk_accelerator().unwrap();
+            assert_eq!(
+                HotKey::from_gtk_accelerator(&accelerator).ok(),
+                Some(hotkey)
+            );
+        }
+
+        let sided = HotKey::new(None, Code::KeyA).with_sides(ModifierSides::CONTROL_RIGHT);
+        assert_eq!(sided.to_gtk_accelerator(), None);
+        assert_eq!(
+            HotKey::from_trigger(None, MouseButton::Back).to_gtk_accelerator(),
+            None
+        );
+        assert_eq!(
+            HotKey::modifiers_only(Modifiers::SHIFT).to_gtk_accelerator(),
+            None
+        );
+    }
+}
@@ -0,0 +1,362 @@
+// Copyright 2022-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::borrow::Cow;
+
+use keyboard_types::{Code, Modifiers};
+
+use super::{HotKey, HotKeySequence, ModifierSides, MouseButton, Trigger, WheelDirection};
+
+/// How [`HotKey::label`] writes a hotkey for humans.
+///
+/// # Examples
+///
+/// ```
+/// # use global_hotkey::hotkey::{HotKey, LabelStyle};
+/// let hotkey: HotKey = "Shift+Super+KeyK".parse().unwrap();
+/// assert_eq!(hotkey.label(LabelStyle::MacOS), "⇧⌘K");
+/// assert_eq!(hotkey.label(LabelStyle::Windows), "Shift+Win+K");
+/// assert_eq!(hotkey.label(LabelStyle::Linux), "Shift+Super+K");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum LabelStyle {
+    /// Modifier glyphs in the `⌃⌥⇧⌘` order followed by the key, like `⇧⌘K`.
+    MacOS,
+    /// Modifier names joined with `+`, like `Ctrl+Shift+K`.
+    Windows,
+    /// Like [`LabelStyle::Windows`], with `Super` instead of `Win`.
+    Linux,
+}
+
+impl LabelStyle {
+    /// Returns the style of the platform the crate is compiled for.
+    pub const fn current() -> Self {
+        #[cfg(target_os = "macos")]
+        return Self::MacOS;
+        #[cfg(windows)]
+        return Self::Windows;
+        #[cfg(not(any(target_os = "macos", windows)))]
+        return Self::Linux;
+    }
+
+    /// Returns the modifier that `CmdOrCtrl` stands for on the platform of this style,
+    /// see [`CMD_OR_CTRL`](super::CMD_OR_CTRL).
+    pub const fn cmd_or_ctrl(self) -> Modifiers {
+        match self {
+            Self::MacOS => Modifiers::SUPER,
+            Self::Windows | Self::Linux => Modifiers::CONTROL,
+        }
+    }
+}
+
+impl Default for LabelStyle {
+    fn default() -> Self {
+        Self::current()
+    }
+}
+
+impl HotKey {
+    /// Returns a label to show this hotkey to users in the given style,
+    /// like `⇧⌘K` or `Ctrl+Shift+K`.
+    ///
+    /// Unlike its [`Display`](std::fmt::Display) form, the label is not meant to be parsed back.
+    pub fn label(&self, style: LabelStyle) -> String {
+        let mut parts = Vec::new();
+        match style {
+            LabelStyle::MacOS => {
+                let mut glyphs = String::new();
+                // Meta is bound to ⌘ and AltGr to ⌥ on macOS
+                for (modifiers, glyph) in [
+                    (Modifiers::CAPS_LOCK, "⇪"),
+                    (Modifiers::HYPER, "✦"),
+                    (Modifiers::CONTROL, "⌃"),
+                    (Modifiers::ALT | Modifiers::ALT_GRAPH, "⌥"),
+                    (Modifiers::SHIFT, "⇧"),
+                    (Modifiers::SUPER | Modifiers::META, "⌘"),
+                ] {
+                    if self.mods.intersects(modifiers) {
+                        glyphs.push_str(glyph);
+                    }
+                }
+                if !glyphs.is_empty() {
+                    parts.push(Cow::Owned(glyphs));
+                }
+            }
+            LabelStyle::Windows | LabelStyle::Linux => {
+                let super_name = match style {
+                    LabelStyle::Windows => "Win",
+                    _ => "Super",
+                };
+                for (modifier, left, right, name) in [
+                    (
+                        Modifiers::CONTROL,
+                        ModifierSides::CONTROL_LEFT,
+                        ModifierSides::CONTROL_RIGHT,
+                        "Ctrl",
+                    ),
+                    (
+                        Modifiers::ALT,
+                        ModifierSides::ALT_LEFT,
+                        ModifierSides::ALT_RIGHT,
+                        "Alt",
+                    ),
+                    (
+                        Modifiers::SHIFT,
+                        ModifierSides::SHIFT_LEFT,
+                        ModifierSides::SHIFT_RIGHT,
+                        "Shift",
+                    ),
+                    (
+                        Modifiers::SUPER,
+                        ModifierSides::SUPER_LEFT,
+                        ModifierSides::SUPER_RIGHT,
+                        super_name,
+                    ),
+                    (
+                        Modifiers::META,
+                        ModifierSides::empty(),
+                        ModifierSides::empty(),
+                        "Meta",
+                    ),
+                    (
+                        Modifiers::HYPER,
+                        ModifierSides::empty(),
+                        ModifierSides::empty(),
+                        "Hyper",
+                    ),
+                    (
+                        Modifiers::ALT_GRAPH,
+                        ModifierSides::empty(),
+                        ModifierSides::empty(),
+                        "AltGr",
+                    ),
+                    (
+                        Modifiers::CAPS_LOCK,
+                        ModifierSides::empty(),
+                        ModifierSides::empty(),
+                        "CapsLock",
+                    ),
+                ] {
+                    if !self.mods.contains(modifier) {
+                        continue;
+                    }
+                    let side = if !left.is_empty() && self.sides.contains(left) {
+                        "Left "
+                    } else if !right.is_empty() && self.sides.contains(right) {
+                        "Right "
+                    } else {
+                        ""
+                    };
+                    parts.push(Cow::Owned(format!("{side}{name}")));
+                }
+            }
+        }
+
+        match self.trigger {
+            Trigger::Key(key) => parts.push(key_label(key, style)),
+            Trigger::Character(' ') => parts.push(key_label(Code::Space, style)),
+            Trigger::Character(c) => parts.push(Cow::Owned(c.to_uppercase().collect())),
+            Trigger::ModifiersOnly => {}
+            Trigger::Mouse(button) => parts.push(match button {
+                MouseButton::Left => Cow::Borrowed("Left Click"),
+                MouseButton::Right => Cow::Borrowed("Right Click"),
+                MouseButton::Middle => Cow::Borrowed("Middle Click"),
+                button => Cow::Owned(format!("Mouse {}", button.number())),
+            }),
+            Trigger::Wheel(direction) => parts.push(Cow::Borrowed(match direction {
+                WheelDirection::Up => "Wheel Up",
+                WheelDirection::Down => "Wheel Down",
+                WheelDirection::Left => "Wheel Left",
+                WheelDirection::Right => "Wheel Right",
+            })),
+        }
+
+        match style {
+            LabelStyle::MacOS => parts.concat(),
+            LabelStyle::Windows | LabelStyle::Linux => parts.join("+"),
+        }
+    }
+}
+
+impl HotKeySequence {
+    /// Returns a label to show this sequence to users in the given style,
+    /// the [`HotKey::label`] of each stroke separated by a space.
+    pub fn label(&self, style: LabelStyle) -> String {
+        self.strokes()
+            .iter()
+            .map(|stroke| stroke.label(style))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Returns the name of a key as printed on keyboards, or its [`Code`] name if it has none.
+fn key_label(key: Code, style: LabelStyle) -> Cow<'static, str> {
+    let mac = style == LabelStyle::MacOS;
+    let label = match key {
+        Code::KeyA => "A",
+        Code::KeyB => "B",
+        Code::KeyC => "C",
+        Code::KeyD => "D",
+        Code::KeyE => "E",
+        Code::KeyF => "F",
+        Code::KeyG => "G",
+        Code::KeyH => "H",
+        Code::KeyI => "I",
+        Code::KeyJ => "J",
+        Code::KeyK => "K",
+        Code::KeyL => "L",
+        Code::KeyM => "M",
+        Code::KeyN => "N",
+        Code::KeyO => "O",
+        Code::KeyP => "P",
+        Code::KeyQ => "Q",
+        Code::KeyR => "R",
+        Code::KeyS => "S",
+        Code::KeyT => "T",
+        Code::KeyU => "U",
+        Code::KeyV => "V",
+        Code::KeyW => "W",
+        Code::KeyX => "X",
+        Code::KeyY => "Y",
+        Code::KeyZ => "Z",
+        Code::Digit0 => "0",
+        Code::Digit1 => "1",
+        Code::Digit2 => "2",
+        Code::Digit3 => "3",
+        Code::Digit4 => "4",
+        Code::Digit5 => "5",
+        Code::Digit6 => "6",
+        Code::Digit7 => "7",
+        Code::Digit8 => "8",
+        Code::Digit9 => "9",
+        Code::Backquote => "`",
+        Code::Backslash => "\\",
+        Code::BracketLeft => "[",
+        Code::BracketRight => "]",
+        Code::Comma => ",",
+        Code::Equal => "=",
+        Code::Minus => "-",
+        Code::Period => ".",
+        Code::Quote => "'",
+        Code::Semicolon => ";",
+        Code::Slash => "/",
+        Code::Backspace if mac => "⌫",
+        Code::Backspace => "Backspace",
+        Code::Delete if mac => "⌦",
+        Code::Delete => "Del",
+        Code::Enter if mac => "↩",
+        Code::Enter => "Enter",
+        Code::Escape if mac => "⎋",
+        Code::Escape => "Esc",
+        Code::Tab if mac => "⇥",
+        Code::Tab => "Tab",
+        Code::CapsLock if mac => "⇪",
+        Code::CapsLock => "CapsLock",
+        Code::Space => "Space",
+        Code::Insert => "Ins",
+        Code::Home if mac => "↖",
+        Code::Home => "Home",
+        Code::End if mac => "↘",
+        Code::End => "End",
+        Code::PageUp if mac => "⇞",
+        Code::PageUp => "PgUp",
+        Code::PageDown if mac => "⇟",
+        Code::PageDown => "PgDn",
+        Code::ArrowUp if mac => "↑",
+        Code::ArrowUp => "Up",
+        Code::ArrowDown if mac => "↓",
+        Code::ArrowDown => "Down",
+        Code::ArrowLeft if mac => "←",
+        Code::ArrowLeft => "Left",
+        Code::ArrowRight if mac => "→",
+        Code::ArrowRight => "Right",
+        Code::PrintScreen => "PrtSc",
+        Code::ScrollLock => "ScrLk",
+        Code::NumLock => "NumLock",
+        Code::Numpad0 => "Num 0",
+        Code::Numpad1 => "Num 1",
+        Code::Numpad2 => "Num 2",
+        Code::Numpad3 => "Num 3",
+        Code::Numpad4 => "Num 4",
+        Code::Numpad5 => "Num 5",
+        Code::Numpad6 => "Num 6",
+        Code::Numpad7 => "Num 7",
+        Code::Numpad8 => "Num 8",
+        Code::Numpad9 => "Num 9",
+        Code::NumpadAdd => "Num +",
+        Code::NumpadDecimal => "Num .",
+        Code::NumpadDivide => "Num /",
+        Code::NumpadEnter => "Num Enter",
+        Code::NumpadEqual => "Num =",
+        Code::NumpadMultiply => "Num *",
+        Code::NumpadSubtract => "Num -",
+        Code::AudioVolumeUp => "Volume Up",
+        Code::AudioVolumeDown => "Volume Down",
+        Code::AudioVolumeMute => "Mute",
+        key => return Cow::Owned(key.to_string()),
+    };
+    Cow::Borrowed(label)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn label(hotkey: &str, style: LabelStyle) -> String {
+        hotkey.parse::<HotKey>().unwrap().label(style)
+    }
+
+    #[test]
+    fn macos_labels() {
+        let style = LabelStyle::MacOS;
+        assert_eq!(label("Super+Shift+KeyK", style), "⇧⌘K");
+        assert_eq!(label("Shift+Super+Alt+Ctrl+KeyK", style), "⌃⌥⇧⌘K");
+        assert_eq!(label("Alt+ArrowUp", style), "⌥↑");
+        assert_eq!(label("RCmd+Enter", style), "⌘↩");
+        assert_eq!(label("Ctrl+Shift", style), "⌃⇧");
+        assert_eq!(label("Super+q", style), "⌘Q");
+        assert_eq!(label("Super+F5", style), "⌘F5");
+    }
+
+    #[test]
+    fn windows_and_linux_labels() {
+        assert_eq!(
+            label("Shift+Ctrl+KeyK", LabelStyle::Windows),
+            "Ctrl+Shift+K"
+        );
+        assert_eq!(label("Super+PageUp", LabelStyle::Windows), "Win+PgUp");
+        assert_eq!(label("Super+PageUp", LabelStyle::Linux), "Super+PgUp");
+        assert_eq!(label("Ctrl+Backquote", LabelStyle::Linux), "Ctrl+`");
+        assert_eq!(label("RCtrl+Space", LabelStyle::Linux), "Right Ctrl+Space");
+        assert_eq!(label("Alt+Mouse4", LabelStyle::Linux), "Alt+Mouse 4");
+        assert_eq!(label("AltGr+e", LabelStyle::Linux), "AltGr+E");
+        assert_eq!(label("Ctrl+Numpad5", LabelStyle::Windows), "Ctrl+Num 5");
+    }
+
+    #[test]
+    fn cmd_or_ctrl() {
+        assert_eq!(LabelStyle::MacOS.cmd_or_ctrl(), Modifiers::SUPER);
+        assert_eq!(LabelStyle::Windows.cmd_or_ctrl(), Modifiers::CONTROL);
+        assert_eq!(
+            LabelStyle::current().cmd_or_ctrl(),
+            crate::hotkey::CMD_OR_CTRL
+        );
+        assert_eq!(
+            label("CmdOrCtrl+Shift+KeyK", LabelStyle::current()),
+            if cfg!(target_os = "macos") {
+                "⇧⌘K"
+            } else {
+                "Ctrl+Shift+K"
+            }
+        );
+    }
+
+    #[test]
+    fn sequence_labels() {
+        let sequence: HotKeySequence = "Ctrl+KeyK Ctrl+KeyC".parse().unwrap();
+        assert_eq!(sequence.label(LabelStyle::Windows), "Ctrl+K Ctrl+C");
+    }
+}
@@ -0,0 +1,496 @@
+// Copyright 2022-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::borrow::Cow;
+
+use keyboard_types::{Code, Modifiers};
+
+use super::{parse_hotkey_with, HotKey, HotKeyParseError, ModifierSides, Trigger, MODIFIER_SIDES};
+
+/// The names of the modifiers and keys in a language, used by [`HotKey::to_localized_string`]
+/// and [`HotKey::parse_localized`].
+///
+/// The modifiers are written in the order of the table, with the first name listed for them,
+/// the other names are only parsed. A name given for a modifier side, like
+/// `(Modifiers::SHIFT, ModifierSides::SHIFT_LEFT, "Left Shift")`, is written for hotkeys
+/// restricted to that side. Keys follow the same rule: the first name listed for a key is written.
+///
+/// Names are matched ignoring case, and names missing from the table fall back to the English
+/// ones of [`HotKey`]'s `FromStr` and `Display` implementations, like `KeyA` for [`Code::KeyA`].
+/// Letters and digits typed by a [`Trigger::Character`] are written as is, like `A`.
+///
+/// # Examples
+///
+/// ```
+/// # use global_hotkey::hotkey::{HotKey, HotKeyLocale};
+/// let hotkey: HotKey = "Ctrl+Shift+A".parse().unwrap();
+/// assert_eq!(hotkey.to_localized_string(&HotKeyLocale::GERMAN), "Strg+Umschalt+A");
+/// assert_eq!(hotkey.to_localized_string(&HotKeyLocale::FRENCH), "Maj+Ctrl+A");
+///
+/// let parsed = HotKey::parse_localized("Strg+Umschalt+A", &HotKeyLocale::GERMAN).unwrap();
+/// assert_eq!(parsed, hotkey);
+/// ```
+///
+/// Tables can be supplied for other languages:
+///
+/// ```
+/// # use global_hotkey::hotkey::{Code, HotKey, HotKeyLocale, ModifierSides, Modifiers};
+/// const DUTCH: HotKeyLocale = HotKeyLocale::new(
+///     &[
+///         (Modifiers::CONTROL, ModifierSides::empty(), "Ctrl"),
+///         (Modifiers::SHIFT, ModifierSides::empty(), "Shift"),
+///     ],
+///     &[(Code::Space, "Spatiebalk")],
+/// );
+/// let hotkey = HotKey::new(Some(Modifiers::CONTROL), Code::Space);
+/// assert_eq!(hotkey.to_localized_string(&DUTCH), "Ctrl+Spatiebalk");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HotKeyLocale {
+    modifiers: &'static [(Modifiers, ModifierSides, &'static str)],
+    keys: &'static [(Code, &'static str)],
+}
+
+impl HotKeyLocale {
+    /// Creates a locale from its modifier and key names.
+    pub const fn new(
+        modifiers: &'static [(Modifiers, ModifierSides, &'static str)],
+        keys: &'static [(Code, &'static str)],
+    ) -> Self {
+        Self { modifiers, keys }
+    }
+
+    /// English names, as printed on US keyboards, like `Ctrl+Shift+A`.
+    pub const ENGLISH: Self = Self::new(
+        &[
+            (Modifiers::CONTROL, ModifierSides::CONTROL_LEFT, "Left Ctrl"),
+            (
+                Modifiers::CONTROL,
+                ModifierSides::CONTROL_RIGHT,
+                "Right Ctrl",
+            ),
+            (Modifiers::CONTROL, ModifierSides::empty(), "Ctrl"),
+            (Modifiers::ALT, ModifierSides::ALT_LEFT, "Left Alt"),
+            (Modifiers::ALT, ModifierSides::ALT_RIGHT, "Right Alt"),
+            (Modifiers::ALT, ModifierSides::empty(), "Alt"),
+            (Modifiers::SHIFT, ModifierSides::SHIFT_LEFT, "Left Shift"),
+            (Modifiers::SHIFT, ModifierSides::SHIFT_RIGHT, "Right Shift"),
+            (Modifiers::SHIFT, ModifierSides::empty(), "Shift"),
+            (Modifiers::SUPER, ModifierSides::SUPER_LEFT, "Left Super"),
+            (Modifiers::SUPER, ModifierSides::SUPER_RIGHT, "Right Super"),
+            (Modifiers::SUPER, ModifierSides::empty(), "Super"),
+            (Modifiers::ALT_GRAPH, ModifierSides::empty(), "AltGr"),
+            (Modifiers::META, ModifierSides::empty(), "Meta"),
+            (Modifiers::HYPER, ModifierSides::empty(), "Hyper"),
+            (Modifiers::CAPS_LOCK, ModifierSides::empty(), "CapsLock"),
+            (Modifiers::SUPER, ModifierSides::empty(), "Win"),
+        ],
+        &[
+            (Code::Space, "Space"),
+            (Code::Enter, "Enter"),
+            (Code::Escape, "Esc"),
+            (Code::Backspace, "Backspace"),
+            (Code::Delete, "Del"),
+            (Code::Insert, "Ins"),
+            (Code::PageUp, "PgUp"),
+            (Code::PageDown, "PgDn"),
+            (Code::PrintScreen, "PrtSc"),
+            (Code::ScrollLock, "ScrLk"),
+        ],
+    );
+
+    /// German names, as printed on German keyboards, like `Strg+Umschalt+A`.
+    pub const GERMAN: Self = Self::new(
+        &[
+            (
+                Modifiers::CONTROL,
+                ModifierSides::CONTROL_LEFT,
+                "Strg links",
+            ),
+            (
+                Modifiers::CONTROL,
+                ModifierSides::CONTROL_RIGHT,
+                "Strg rechts",
+            ),
+            (Modifiers::CONTROL, ModifierSides::empty(), "Strg"),
+            (
+                Modifiers::SHIFT,
+                ModifierSides::SHIFT_LEFT,
+                "Umschalt links",
+            ),
+            (
+                Modifiers::SHIFT,
+                ModifierSides::SHIFT_RIGHT,
+                "Umschalt rechts",
+            ),
+            (Modifiers::SHIFT, ModifierSides::empty(), "Umschalt"),
+            (Modifiers::ALT, ModifierSides::ALT_LEFT, "Alt links"),
+            (Modifiers::ALT, ModifierSides::ALT_RIGHT, "Alt rechts"),
+            (Modifiers::ALT, ModifierSides::empty(), "Alt"),
+            (Modifiers::ALT_GRAPH, ModifierSides::empty(), "Alt Gr"),
+            (Modifiers::SUPER, ModifierSides::SUPER_LEFT, "Win links"),
+            (Modifiers::SUPER, ModifierSides::SUPER_RIGHT, "Win rechts"),
+            (Modifiers::SUPER, ModifierSides::empty(), "Win"),
+            (Modifiers::META, ModifierSides::empty(), "Meta"),
+            (Modifiers::HYPER, ModifierSides::empty(), "Hyper"),
+            (Modifiers::CAPS_LOCK, ModifierSides::empty(), "Feststell"),
+            (Modifiers::CONTROL, ModifierSides::empty(), "Steuerung"),
+            (Modifiers::ALT_GRAPH, ModifierSides::empty(), "AltGr"),
+        ],
+        &[
+            (Code::Space, "Leertaste"),
+            (Code::Enter, "Eingabe"),
+            (Code::Escape, "Esc"),
+            (Code::Backspace, "Rücktaste"),
+            (Code::Tab, "Tab"),
+            (Code::Delete, "Entf"),
+            (Code::Insert, "Einfg"),
+            (Code::Home, "Pos1"),
+            (Code::End, "Ende"),
+            (Code::PageUp, "Bild auf"),
+            (Code::PageDown, "Bild ab"),
+            (Code::ArrowUp, "Pfeil oben"),
+            (Code::ArrowDown, "Pfeil unten"),
+            (Code::ArrowLeft, "Pfeil links"),
+            (Code::ArrowRight, "Pfeil rechts"),
+            (Code::PrintScreen, "Druck"),
+            (Code::ScrollLock, "Rollen"),
+            (Code::Pause, "Pause"),
+            (Code::NumLock, "Num"),
+            (Code::CapsLock, "Feststell"),
+            (Code::Enter, "Enter"),
+        ],
+    );
+
+    /// French names, as printed on French keyboards, like `Maj+Ctrl+A`.
+    pub const FRENCH: Self = Self::new(
+        &[
+            (Modifiers::SHIFT, ModifierSides::SHIFT_LEFT, "Maj gauche"),
+            (Modifiers::SHIFT, ModifierSides::SHIFT_RIGHT, "Maj droite"),
+            (Modifiers::SHIFT, ModifierSides::empty(), "Maj"),
+            (
+                Modifiers::CONTROL,
+                ModifierSides::CONTROL_LEFT,
+                "Ctrl gauche",
+            ),
+            (
+                Modifiers::CONTROL,
+                ModifierSides::CONTROL_RIGHT,
+                "Ctrl droite",
+            ),
+            (Modifiers::CONTROL, ModifierSides::empty(), "Ctrl"),
+            (Modifiers::ALT, ModifierSides::ALT_LEFT, "Alt gauche"),
+            (Modifiers::ALT, ModifierSides::ALT_RIGHT, "Alt droite"),
+            (Modifiers::ALT, ModifierSides::empty(), "Alt"),
+            (Modifiers::ALT_GRAPH, ModifierSides::empty(), "Alt Gr"),
+            (Modifiers::SUPER, ModifierSides::SUPER_LEFT, "Win gauche"),
+            (Modifiers::SUPER, ModifierSides::SUPER_RIGHT, "Win droite"),
+            (Modifiers::SUPER, ModifierSides::empty(), "Win"),
+            (Modifiers::META, ModifierSides::empty(), "Méta"),
+            (Modifiers::HYPER, ModifierSides::empty(), "Hyper"),
+            (Modifiers::CAPS_LOCK, ModifierSides::empty(), "Verr Maj"),
+            (Modifiers::SHIFT, ModifierSides::empty(), "Majuscule"),
+            (Modifiers::ALT_GRAPH, ModifierSides::empty(), "AltGr"),
+        ],
+        &[
+            (Code::Space, "Espace"),
+            (Code::Enter, "Entrée"),
+            (Code::Escape, "Échap"),
+            (Code::Backspace, "Retour arrière"),
+            (Code::Tab, "Tab"),
+            (Code::Delete, "Suppr"),
+            (Code::Insert, "Inser"),
+            (Code::Home, "Origine"),
+            (Code::End, "Fin"),
+            (Code::PageUp, "Page préc"),
+            (Code::PageDown, "Page suiv"),
+            (Code::ArrowUp, "Haut"),
+            (Code::ArrowDown, "Bas"),
+            (Code::ArrowLeft, "Gauche"),
+            (Code::ArrowRight, "Droite"),
+            (Code::PrintScreen, "Impr écran"),
+            (Code::ScrollLock, "Arrêt défil"),
+            (Code::Pause, "Pause"),
+            (Code::NumLock, "Verr Num"),
+            (Code::CapsLock, "Verr Maj"),
+            (Code::Escape, "Echap"),
+            (Code::Enter, "Entree"),
+        ],
+    );
+
+    /// Japanese names, as printed on JIS keyboards, like `Ctrl+Shift+スペース`.
+    pub const JAPANESE: Self = Self::new(
+        &[
+            (Modifiers::CONTROL, ModifierSides::CONTROL_LEFT, "左Ctrl"),
+            (Modifiers::CONTROL, ModifierSides::CONTROL_RIGHT, "右Ctrl"),
+            (Modifiers::CONTROL, ModifierSides::empty(), "Ctrl"),
+            (Modifiers::ALT, ModifierSides::ALT_LEFT, "左Alt"),
+            (Modifiers::ALT, ModifierSides::ALT_RIGHT, "右Alt"),
+            (Modifiers::ALT, ModifierSides::empty(), "Alt"),
+            (Modifiers::SHIFT, ModifierSides::SHIFT_LEFT, "左Shift"),
+            (Modifiers::SHIFT, ModifierSides::SHIFT_RIGHT, "右Shift"),
+            (Modifiers::SHIFT, ModifierSides::empty(), "Shift"),
+            (Modifiers::SUPER, ModifierSides::SUPER_LEFT, "左Win"),
+            (Modifiers::SUPER, ModifierSides::SUPER_RIGHT, "右Win"),
+            (Modifiers::SUPER, ModifierSides::empty(), "Win"),
+            (Modifiers::ALT_GRAPH, ModifierSides::empty(), "AltGr"),
+            (Modifiers::META, ModifierSides::empty(), "Meta"),
+            (Modifiers::HYPER, ModifierSides::empty(), "Hyper"),
+            (Modifiers::CAPS_LOCK, ModifierSides::empty(), "英数"),
+        ],
+        &[
+            (Code::Space, "スペース"),
+            (Code::Enter, "エンター"),
+            (Code::Escape, "エスケープ"),
+            (Code::Backspace, "バックスペース"),
+            (Code::Tab, "タブ"),
+            (Code::Delete, "デリート"),
+            (Code::Insert, "インサート"),
+            (Code::Home, "ホーム"),
+            (Code::End, "エンド"),
+            (Code::PageUp, "ページアップ"),
+            (Code::PageDown, "ページダウン"),
+            (Code::ArrowUp, "上"),
+            (Code::ArrowDown, "下"),
+            (Code::ArrowLeft, "左"),
+            (Code::ArrowRight, "右"),
+            (Code::Backquote, "半角/全角"),
+            (Code::Convert, "変換"),
+            (Code::NonConvert, "無変換"),
+            (Code::KanaMode, "カタカナひらがな"),
+            (Code::CapsLock, "英数"),
+        ],
+    );
+
+    /// Returns the modifiers and sides named `token`.
+    pub(crate) fn modifier(&self, token: &str) -> Option<(Modifiers, ModifierSides)> {
+        let token = token.to_lowercase();
+        self.modifiers
+            .iter()
+            .find(|(_, _, name)| name.to_lowercase() == token)
+            .map(|&(modifiers, sides, _)| (modifiers, sides))
+    }
+
+    /// Returns the key named `token`.
+    pub(crate) fn key(&self, token: &str) -> Option<Code> {
+        let token = token.to_lowercase();
+        self.keys
+            .iter()
+            .find(|(_, name)| name.to_lowercase() == token)
+            .map(|&(code, _)| code)
+    }
+
+    pub(crate) fn modifier_names(&self) -> impl Iterator<Item = &'static str> {
+        self.modifiers.iter().map(|&(_, _, name)| name)
+    }
+
+    pub(crate) fn key_names(&self) -> impl Iterator<Item = &'static str> {
+        self.keys.iter().map(|&(_, name)| name)
+    }
+}
+
+impl Default for HotKeyLocale {
+    fn default() -> Self {
+        Self::ENGLISH
+    }
+}
+
+impl HotKey {
+    /// Parses a hotkey written with the names of `locale`, like `Strg+Umschalt+A`
+    /// with [`HotKeyLocale::GERMAN`].
+    ///
+    /// The English names accepted by `FromStr` are accepted too.
+    pub fn parse_localized(hotkey: &str, locale: &HotKeyLocale) -> Result<Self, HotKeyParseError> {
+        parse_hotkey_with(hotkey, Some(locale))
+    }
+
+    /// Returns this hotkey written with the names of `locale`, like `Strg+Umschalt+A`
+    /// with [`HotKeyLocale::GERMAN`], see [`HotKeyLocale`].
+    ///
+    /// It parses back with [`HotKey::parse_localized`] to an equal hotkey.
+    pub fn to_localized_string(&self, locale: &HotKeyLocale) -> String {
+        let mut parts = Vec::new();
+        let mut written = Modifiers::empty();
+        // CapsLock comes first as it is the CapsLock key when it comes last
+        if self.mods.contains(Modifiers::CAPS_LOCK) {
+            let localized = locale
+                .modifiers
+                .iter()
+                .find(|&&(modifiers, _, _)| modifiers == Modifiers::CAPS_LOCK);
+            parts.push(Cow::Borrowed(
+                localized.map_or("CapsLock", |&(_, _, name)| name),
+            ));
+            written |= Modifiers::CAPS_LOCK;
+        }
+        for &(modifiers, sides, name) in locale.modifiers {
+            let own_sides = self.sides & sides_of(modifiers);
+            if modifiers.is_empty()
+                || written.intersects(modifiers)
+                || !self.mods.contains(modifiers)
+                || sides != own_sides
+            {
+                continue;
+            }
+            parts.push(Cow::Borrowed(name));
+            written |= modifiers;
+        }
+        let rest = self.mods - written;
+        if !rest.is_empty() {
+            let rest = HotKey::modifiers_only(rest).with_sides(self.sides & sides_of(rest));
+            parts.push(Cow::Owned(rest.to_string()));
+        }
+
+        let key = |code: Code| {
+            let localized = locale.keys.iter().find(|&&(key, _)| key == code);
+            localized.map(|&(_, name)| Cow::Borrowed(name))
+        };
+        match self.trigger {
+            Trigger::ModifiersOnly => {}
+            // letters and digits are left to the characters, `A` parses back as `Character('a')`
+            Trigger::Key(code) => parts.push(
+                key(code)
+                    .unwrap_or_else(|| Cow::Owned(HotKey::from_trigger(None, code).to_string())),
+            ),
+            Trigger::Character(c) if c.is_alphanumeric() => {
+                let upper: String = c.to_uppercase().collect();
+                // characters like `ß` have no uppercase character that parses back to them
+                if upper.chars().count() == 1 && upper.to_lowercase() == c.to_string() {
+                    parts.push(Cow::Owned(upper))
+                } else {
+                    parts.push(Cow::Owned(c.to_string()))
+                }
+            }
+            Trigger::Character(c) if c.is_ascii_punctuation() && c != '+' => {
+                parts.push(Cow::Owned(c.to_string()))
+            }
+            trigger => parts.push(Cow::Owned(HotKey::from_trigger(None, trigger).to_string())),
+        }
+
+        parts.join("+")
+    }
+}
+
+/// Returns the sides of `modifiers`.
+fn sides_of(modifiers: Modifiers) -> ModifierSides {
+    MODIFIER_SIDES
+        .iter()
+        .filter(|(modifier, _, _)| modifiers.contains(*modifier))
+        .fold(ModifierSides::empty(), |sides, &(_, left, right)| {
+            sides | left | right
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn localized_strings() {
+        let hotkey: HotKey = "Ctrl+Shift+A".parse().unwrap();
+        assert_eq!(
+            hotkey.to_localized_string(&HotKeyLocale::ENGLISH),
+            "Ctrl+Shift+A"
+        );
+        assert_eq!(
+            hotkey.to_localized_string(&HotKeyLocale::GERMAN),
+            "Strg+Umschalt+A"
+        );
+        assert_eq!(
+            hotkey.to_localized_string(&HotKeyLocale::FRENCH),
+            "Maj+Ctrl+A"
+        );
+        assert_eq!(
+            hotkey.to_localized_string(&HotKeyLocale::JAPANESE),
+            "Ctrl+Shift+A"
+        );
+
+        let hotkey: HotKey = "RCtrl+Alt+PageDown".parse().unwrap();
+        assert_eq!(
+            hotkey.to_localized_string(&HotKeyLocale::GERMAN),
+            "Strg rechts+Alt+Bild ab"
+        );
+        assert_eq!(
+            hotkey.to_localized_string(&HotKeyLocale::JAPANESE),
+            "右Ctrl+Alt+ページダウン"
+        );
+
+        // modifiers and keys missing from the table keep their English name
+        let hotkey: HotKey = "LShift+Super+Mouse4".parse().unwrap();
+        const LOCALE: HotKeyLocale =
+            HotKeyLocale::new(&[(Modifiers::SUPER, ModifierSides::empty(), "Cmd")], &[]);
+        assert_eq!(hotkey.to_localized_string(&LOCALE), "Cmd+LShift+Mouse4");
+        let hotkey = HotKey::new(None, Code::ShiftLeft);
+        assert_eq!(hotkey.to_localized_string(&LOCALE), "Code(ShiftLeft)");
+        let hotkey = HotKey::from_trigger(Some(Modifiers::CONTROL), '+');
+        assert_eq!(hotkey.to_localized_string(&LOCALE), "Control+Plus");
+        let hotkey = HotKey::modifiers_only(Modifiers::CAPS_LOCK | Modifiers::SUPER);
+        assert_eq!(hotkey.to_localized_string(&LOCALE), "CapsLock+Cmd");
+    }
+
+    #[test]
+    fn parse_localized() {
+        let german = &HotKeyLocale::GERMAN;
+        let parse = |hotkey| HotKey::parse_localized(hotkey, german).unwrap();
+        assert_eq!(parse("Strg+Umschalt+A"), "Ctrl+Shift+A".parse().unwrap());
+        assert_eq!(
+            parse("strg + umschalt + a"),
+            "Ctrl+Shift+A".parse().unwrap()
+        );
+        assert_eq!(
+            parse("Strg links+Leertaste"),
+            "LCtrl+Space".parse().unwrap()
+        );
+        assert_eq!(parse("Strg+Feststell"), "Ctrl+CapsLock".parse().unwrap());
+        assert_eq!(parse("Feststell+Strg"), "CapsLock+Ctrl".parse().unwrap());
+        assert_eq!(parse("Feststell"), "CapsLock".parse().unwrap());
+        assert_eq!(parse("Strg+Shift+F5"), "Ctrl+Shift+F5".parse().unwrap());
+
+        let french = &HotKeyLocale::FRENCH;
+        let hotkey = HotKey::parse_localized("Maj+Ctrl+Échap", french).unwrap();
+        assert_eq!(hotkey, "Ctrl+Shift+Escape".parse().unwrap());
+        let hotkey = HotKey::parse_localized("MAJ+ÉCHAP", french).unwrap();
+        assert_eq!(hotkey, "Shift+Escape".parse().unwrap());
+
+        let error = HotKey::parse_localized("Strk+A", german).unwrap_err();
+        assert_eq!(error.suggestions()[0], "Strg");
+
+        // the default parser stays English-only
+        assert!("Strg+A".parse::<HotKey>().is_err());
+    }
+
+    #[test]
+    fn localized_round_trip() {
+        let locales = [
+            HotKeyLocale::ENGLISH,
+            HotKeyLocale::GERMAN,
+            HotKeyLocale::FRENCH,
+            HotKeyLocale::JAPANESE,
+        ];
+        for locale in &locales {
+            for hotkey in [
+                "Shift+Control+KeyK",
+                "Control+Digit1",
+                "Control+1",
+                "Shift+Char(0x20)",
+                "Alt+ß",
+                "LShift+RAlt+Space",
+                "Control+a",
+                "Alt+Plus",
+                "Super+Mouse4",
+                "Control+WheelUp",
+                "Shift+Control",
+                "CapsLock+Shift",
+                "Ctrl+CapsLock",
+                "Meta+Hyper+AltGraph+F5",
+                "Alt+ArrowLeft",
+                "RSuper+Code(ShiftLeft)",
+            ] {
+                let hotkey: HotKey = hotkey.parse().unwrap();
+                let string = hotkey.to_localized_string(locale);
+                let parsed = HotKey::parse_localized(&string, locale).unwrap();
+                assert_eq!(parsed, hotkey, "{string}");
+            }
+        }
+    }
+}
@@ -0,0 +1,511 @@
+// Copyright 2022-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use keyboard_types::{Code, Modifiers};
+
+use super::{HotKey, HotKeyParseError, HotKeyParseErrorKind, HotKeySequence, Trigger};
+
+/// Vim names of the special keys, the first name of a key is the one written by [`HotKey::to_vim`].
+const VIM_KEYS: &[(&str, Trigger)] = &[
+    ("CR", Trigger::Key(Code::Enter)),
+    ("Return", Trigger::Key(Code::Enter)),
+    ("Enter", Trigger::Key(Code::Enter)),
+    ("Esc", Trigger::Key(Code::Escape)),
+    ("Tab", Trigger::Key(Code::Tab)),
+    ("BS", Trigger::Key(Code::Backspace)),
+    ("Del", Trigger::Key(Code::Delete)),
+    ("Space", Trigger::Key(Code::Space)),
+    ("Insert", Trigger::Key(Code::Insert)),
+    ("Home", Trigger::Key(Code::Home)),
+    ("End", Trigger::Key(Code::End)),
+    ("PageUp", Trigger::Key(Code::PageUp)),
+    ("PageDown", Trigger::Key(Code::PageDown)),
+    ("Up", Trigger::Key(Code::ArrowUp)),
+    ("Down", Trigger::Key(Code::ArrowDown)),
+    ("Left", Trigger::Key(Code::ArrowLeft)),
+    ("Right", Trigger::Key(Code::ArrowRight)),
+    ("k0", Trigger::Key(Code::Numpad0)),
+    ("k1", Trigger::Key(Code::Numpad1)),
+    ("k2", Trigger::Key(Code::Numpad2)),
+    ("k3", Trigger::Key(Code::Numpad3)),
+    ("k4", Trigger::Key(Code::Numpad4)),
+    ("k5", Trigger::Key(Code::Numpad5)),
+    ("k6", Trigger::Key(Code::Numpad6)),
+    ("k7", Trigger::Key(Code::Numpad7)),
+    ("k8", Trigger::Key(Code::Numpad8)),
+    ("k9", Trigger::Key(Code::Numpad9)),
+    ("kPlus", Trigger::Key(Code::NumpadAdd)),
+    ("kMinus", Trigger::Key(Code::NumpadSubtract)),
+    ("kMultiply", Trigger::Key(Code::NumpadMultiply)),
+    ("kDivide", Trigger::Key(Code::NumpadDivide)),
+    ("kPoint", Trigger::Key(Code::NumpadDecimal)),
+    ("kEnter", Trigger::Key(Code::NumpadEnter)),
+    ("lt", Trigger::Character('<')),
+    ("Bar", Trigger::Character('|')),
+    ("Bslash", Trigger::Character('\\')),
+];
+
+/// Emacs names of the special keys, the first name of a key is the one written by [`HotKey::to_emacs`].
+///
+/// Names in angle brackets are Emacs function keys, the others are written as is.
+const EMACS_KEYS: &[(&str, Trigger)] = &[
+    ("RET", Trigger::Key(Code::Enter)),
+    ("<return>", Trigger::Key(Code::Enter)),
+    ("ESC", Trigger::Key(Code::Escape)),
+    ("<escape>", Trigger::Key(Code::Escape)),
+    ("TAB", Trigger::Key(Code::Tab)),
+    ("<tab>", Trigger::Key(Code::Tab)),
+    ("SPC", Trigger::Key(Code::Space)),
+    ("DEL", Trigger::Key(Code::Backspace)),
+    ("<backspace>", Trigger::Key(Code::Backspace)),
+    ("<delete>", Trigger::Key(Code::Delete)),
+    ("<insert>", Trigger::Key(Code::Insert)),
+    ("<home>", Trigger::Key(Code::Home)),
+    ("<end>", Trigger::Key(Code::End)),
+    ("<prior>", Trigger::Key(Code::PageUp)),
+    ("<next>", Trigger::Key(Code::PageDown)),
+    ("<up>", Trigger::Key(Code::ArrowUp)),
+    ("<down>", Trigger::Key(Code::ArrowDown)),
+    ("<left>", Trigger::Key(Code::ArrowLeft)),
+    ("<right>", Trigger::Key(Code::ArrowRight)),
+    ("<kp-0>", Trigger::Key(Code::Numpad0)),
+    ("<kp-1>", Trigger::Key(Code::Numpad1)),
+    ("<kp-2>", Trigger::Key(Code::Numpad2)),
+    ("<kp-3>", Trigger::Key(Code::Numpad3)),
+    ("<kp-4>", Trigger::Key(Code::Numpad4)),
+    ("<kp-5>", Trigger::Key(Code::Numpad5)),
+    ("<kp-6>", Trigger::Key(Code::Numpad6)),
+    ("<kp-7>", Trigger::Key(Code::Numpad7)),
+    ("<kp-8>", Trigger::Key(Code::Numpad8)),
+    ("<kp-9>", Trigger::Key(Code::Numpad9)),
+    ("<kp-add>", Trigger::Key(Code::NumpadAdd)),
+    ("<kp-subtract>", Trigger::Key(Code::NumpadSubtract)),
+    ("<kp-multiply>", Trigger::Key(Code::NumpadMultiply)),
+    ("<kp-divide>", Trigger::Key(Code::NumpadDivide)),
+    ("<kp-decimal>", Trigger::Key(Code::NumpadDecimal)),
+    ("<kp-enter>", Trigger::Key(Code::NumpadEnter)),
+];
+
+/// Modifier prefixes of the Vim notation, in the order written by [`HotKey::to_vim`].
+///
+/// Vim doesn't tell Meta and Alt apart, both are the Alt key.
+const VIM_MODIFIERS: &[(char, Modifiers)] = &[
+    ('C', Modifiers::CONTROL),
+    ('S', Modifiers::SHIFT),
+    ('M', Modifiers::ALT),
+    ('A', Modifiers::ALT),
+    ('D', Modifiers::SUPER),
+];
+
+/// Modifier prefixes of the Emacs notation, in the order written by [`HotKey::to_emacs`].
+///
+/// Emacs' Meta is the Alt key of PC keyboards, so like in Vim both `M` and `A` are the Alt key.
+const EMACS_MODIFIERS: &[(char, Modifiers)] = &[
+    ('C', Modifiers::CONTROL),
+    ('H', Modifiers::HYPER),
+    ('M', Modifiers::ALT),
+    ('A', Modifiers::ALT),
+    ('S', Modifiers::SHIFT),
+    ('s', Modifiers::SUPER),
+];
+
+/// Returns the function key `F<n>` of `name`, ignoring the case of the `F`.
+fn function_key(name: &str) -> Option<Code> {
+    let n: u8 = name.strip_prefix(['F', 'f'])?.parse().ok()?;
+    super::parse_key(&format!("F{n}"))
+}
+
+/// Returns the number `n` of the function key `F<n>`.
+fn function_key_number(key: Code) -> Option<u8> {
+    (1..=24).find(|&n| function_key(&format!("F{n}")) == Some(key))
+}
+
+/// Splits the modifier prefixes, like `C-S-`, from the key, which is empty if missing.
+fn split_modifiers<'a>(
+    notation: &'a str,
+    prefixes: &[(char, Modifiers)],
+    ignore_case: bool,
+) -> (Modifiers, &'a str) {
+    let mut mods = Modifiers::empty();
+    let mut rest = notation;
+    loop {
+        let mut chars = rest.chars();
+        let (Some(prefix), Some('-')) = (chars.next(), chars.next()) else {
+            break;
+        };
+        // a lone `-` after a prefix is the minus key, like in `C--`
+        if chars.as_str().is_empty() {
+            break;
+        }
+        let Some((_, modifier)) = prefixes
+            .iter()
+            .find(|(p, _)| *p == prefix || (ignore_case && p.eq_ignore_ascii_case(&prefix)))
+        else {
+            break;
+        };
+        mods |= *modifier;
+        rest = chars.as_str();
+    }
+    (mods, rest)
+}
+
+/// Returns the single character of `key`, if it is one.
+fn single_char(key: &str) -> Option<char> {
+    let mut chars = key.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(c),
+        _ => None,
+    }
+}
+
+impl HotKey {
+    /// Parses a key in Vim notation, like `<C-S-a>`, `<M-Space>`, `<CR>` or `a`.
+    ///
+    /// The `C`, `S`, `M` or `A`, and `D` prefixes stand for Control, Shift, Alt and Super,
+    /// in any case.
+    /// Like in Vim, the case of a letter only matters without modifiers, `A` is `Shift+a`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use global_hotkey::hotkey::{HotKey, Modifiers, Code};
+    /// let hotkey = HotKey::from_vim("<M-Space>").unwrap();
+    /// assert_eq!(hotkey, HotKey::new(Some(Modifiers::ALT), Code::Space));
+    /// assert_eq!(hotkey.to_vim().unwrap(), "<M-Space>");
+    /// ```
+    pub fn from_vim(notation: &str) -> Result<Self, HotKeyParseError> {
+        use HotKeyParseErrorKind::*;
+
+        let trimmed = notation.trim();
+        let Some(inner) = trimmed
+            .strip_prefix('<')
+            .and_then(|n| n.strip_suffix('>'))
+            .filter(|n| !n.is_empty())
+        else {
+            let c = single_char(trimmed)
+                .ok_or_else(|| HotKeyParseError::new(InvalidFormat, notation, trimmed))?;
+            return Ok(vim_char(Modifiers::empty(), c));
+        };
+
+        let (mods, key) = split_modifiers(inner, VIM_MODIFIERS, true);
+        if key.is_empty() {
+            return Err(HotKeyParseError::new(EmptyToken, notation, key));
+        }
+        if let Some(c) = single_char(key) {
+            return Ok(vim_char(mods, c));
+        }
+        let trigger = VIM_KEYS
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(key))
+            .map(|(_, trigger)| *trigger)
+            .or_else(|| function_key(key).map(Trigger::Key))
+            .ok_or_else(|| {
+                HotKeyParseError::new(UnsupportedKey, notation, key)
+                    .suggest(VIM_KEYS.iter().map(|(name, _)| *name))
+            })?;
+        Ok(HotKey::from_trigger(Some(mods), trigger))
+    }
+
+    /// Writes this hotkey in Vim notation, like `<C-S-a>`.
+    ///
+    /// Returns `None` if Vim can't describe this hotkey, for example when it uses
+    /// [`Modifiers::HYPER`] or is restricted to a side of a modifier.
+    pub fn to_vim(&self) -> Option<String> {
+        if !self.sides.is_empty() {
+            return None;
+        }
+        let key = match self.trigger {
+            Trigger::Character(c) if self.mods.is_empty() && c != '<' && c != ' ' => {
+                return Some(c.to_string())
+            }
+            Trigger::Character(c) if self.mods == Modifiers::SHIFT && c.is_ascii_lowercase() => {
+                return Some(c.to_ascii_uppercase().to_string())
+            }
+            trigger => match VIM_KEYS.iter().find(|(_, t)| *t == trigger) {
+                Some((name, _)) => name.to_string(),
+                None => match trigger {
+                    Trigger::Character(c) => c.to_string(),
+                    Trigger::Key(key) => format!("F{}", function_key_number(key)?),
+                    _ => return None,
+                },
+            },
+        };
+        let prefixes = write_modifiers(self.mods, VIM_MODIFIERS)?;
+        Some(format!("<{prefixes}{key}>"))
+    }
+
+    /// Parses a key in Emacs notation, like `C-M-%`, `s-k`, `RET` or `C-<f5>`.
+    ///
+    /// The `C`, `H`, `M` or `A`, `S` and `s` prefixes stand for Control, Hyper, Alt, Shift and Super.
+    /// Uppercase letters are shifted, `C-A` is `C-S-a`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use global_hotkey::hotkey::{HotKey, Modifiers};
+    /// let hotkey = HotKey::from_emacs("C-M-%").unwrap();
+    /// assert_eq!(hotkey, HotKey::from_trigger(Some(Modifiers::CONTROL | Modifiers::ALT), '%'));
+    /// assert_eq!(hotkey.to_emacs().unwrap(), "C-M-%");
+    /// ```
+    pub fn from_emacs(notation: &str) -> Result<Self, HotKeyParseError> {
+        use HotKeyParseErrorKind::*;
+
+        let (mut mods, key) = split_modifiers(notation.trim(), EMACS_MODIFIERS, false);
+        if key.is_empty() {
+            return Err(HotKeyParseError::new(EmptyToken, notation, key));
+        }
+        if let Some(c) = single_char(key) {
+            if c.is_ascii_uppercase() {
+                mods |= Modifiers::SHIFT;
+            }
+            return Ok(HotKey::from_trigger(Some(mods), c));
+        }
+
+        let trigger = EMACS_KEYS
+            .iter()
+            .find(|(name, _)| *name == key)
+            .map(|(_, trigger)| *trigger)
+            .or_else(|| {
+                key.strip_prefix('<')
+                    .and_then(|k| k.strip_suffix('>'))
+                    .and_then(function_key)
+                    .map(Trigger::Key)
+            })
+            .ok_or_else(|| {
+                HotKeyParseError::new(UnsupportedKey, notation, key)
+                    .suggest(EMACS_KEYS.iter().map(|(name, _)| *name))
+            })?;
+        Ok(HotKey::from_trigger(Some(mods), trigger))
+    }
+
+    /// Writes this hotkey in Emacs notation, like `C-M-%`.
+    ///
+    /// Returns `None` if Emacs can't describe this hotkey, for example when it uses
+    /// [`Modifiers::META`] or is restricted to a side of a modifier. The space character
+    /// has no notation either, as `SPC` is the [`Code::Space`] key.
+    pub fn to_emacs(&self) -> Option<String> {
+        if !self.sides.is_empty() {
+            return None;
+        }
+        let key = match self.trigger {
+            Trigger::Character(c) if self.mods == Modifiers::SHIFT && c.is_ascii_lowercase() => {
+                return Some(c.to_ascii_uppercase().to_string())
+            }
+            Trigger::Character(' ') => return None,
+            Trigger::Character(c) => c.to_string(),
+            trigger => match EMACS_KEYS.iter().find(|(_, t)| *t == trigger) {
+                Some((name, _)) => name.to_string(),
+                None => match trigger {
+                    Trigger::Key(key) => format!("<f{}>", function_key_number(key)?),
+                    _ => return None,
+                },
+            },
+        };
+        let prefixes = write_modifiers(self.mods, EMACS_MODIFIERS)?;
+        Some(format!("{prefixes}{key}"))
+    }
+}
+
+impl HotKeySequence {
+    /// Parses a sequence in Vim notation, like `<C-x><C-f>`, see [`HotKey::from_vim`].
+    pub fn from_vim(notation: &str) -> Result<Self, HotKeyParseError> {
+        let mut strokes = Vec::new();
+        let mut rest = notation.trim();
+        while let Some(c) = rest.chars().next() {
+            let len = match rest.find('>') {
+                Some(end) if c == '<' && end > 1 => end + 1,
+                _ => c.len_utf8(),
+            };
+            let stroke = &rest[..len];
+            strokes.push(HotKey::from_vim(stroke).map_err(|e| e.within(notation, stroke))?);
+            rest = &rest[len..];
+        }
+        if strokes.is_empty() {
+            let kind = HotKeyParseErrorKind::EmptyToken;
+            return Err(HotKeyParseError::new(kind, notation, notation.trim()));
+        }
+        Ok(HotKeySequence::new(strokes))
+    }
+
+    /// Writes this sequence in Vim notation, like `<C-x><C-f>`, see [`HotKey::to_vim`].
+    pub fn to_vim(&self) -> Option<String> {
+        self.strokes().iter().map(HotKey::to_vim).collect()
+    }
+
+    /// Parses a sequence in Emacs notation, like `C-x C-f`, see [`HotKey::from_emacs`].
+    pub fn from_emacs(notation: &str) -> Result<Self, HotKeyParseError> {
+        let strokes = notation
+            .split_whitespace()
+            .map(|stroke| HotKey::from_emacs(stroke).map_err(|e| e.within(notation, stroke)))
+            .collect::<Result<Vec<_>, _>>()?;
+        if strokes.is_empty() {
+            let kind = HotKeyParseErrorKind::EmptyToken;
+            return Err(HotKeyParseError::new(kind, notation, notation.trim()));
+        }
+        Ok(HotKeySequence::new(strokes))
+    }
+
+    /// Writes this sequence in Emacs notation, like `C-x C-f`, see [`HotKey::to_emacs`].
+    pub fn to_emacs(&self) -> Option<String> {
+        let strokes = self
+            .strokes()
+            .iter()
+            .map(HotKey::to_emacs)
+            .collect::<Option<Vec<_>>>()?;
+        Some(strokes.join(" "))
+    }
+}
+
+/// Returns a hotkey for a Vim character, an uppercase letter without modifiers is shifted.
+fn vim_char(mut mods: Modifiers, c: char) -> HotKey {
+    if mods.is_empty() && c.is_ascii_uppercase() {
+        mods = Modifiers::SHIFT;
+    }
+    HotKey::from_trigger(Some(mods), c)
+}
+
+/// Writes the prefixes of `mods`, like `C-S-`, or returns `None` if some of them have no prefix.
+///
+/// A modifier with several prefixes is written with the first one.
+fn write_modifiers(mods: Modifiers, prefixes: &[(char, Modifiers)]) -> Option<String> {
+    let mut written = String::new();
+    let mut rest = mods;
+    for &(prefix, modifier) in prefixes {
+        if rest.contains(modifier) {
+            written.push(prefix);
+            written.push('-');
+            rest.remove(modifier);
+        }
+    }
+    rest.is_empty().then_some(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vim_notation() {
+        let parse = |s| HotKey::from_vim(s).unwrap();
+        let ctrl_shift = Some(Modifiers::CONTROL | Modifiers::SHIFT);
+
+        assert_eq!(parse("<C-S-a>"), HotKey::from_trigger(ctrl_shift, 'a'));
+        assert_eq!(parse("<c-s-A>"), parse("<C-S-a>"));
+        assert_eq!(parse("<M-Space>"), parse("<A-space>"));
+        assert_eq!(parse("<CR>"), HotKey::new(None, Code::Enter));
+        assert_eq!(parse("<Esc>"), HotKey::new(None, Code::Escape));
+        assert_eq!(
+            parse("<C-Tab>"),
+            HotKey::new(Some(Modifiers::CONTROL), Code::Tab)
+        );
+        assert_eq!(
+            parse("<D-F12>"),
+            HotKey::new(Some(Modifiers::SUPER), Code::F12)
+        );
+        assert_eq!(
+            parse("<C-->"),
+            HotKey::from_trigger(Some(Modifiers::CONTROL), '-')
+        );
+        assert_eq!(parse("<lt>"), HotKey::from_trigger(None, '<'));
+        assert_eq!(
+            parse("A"),
+            HotKey::from_trigger(Some(Modifiers::SHIFT), 'a')
+        );
+        let error = HotKey::from_vim("<C-Esq>").unwrap_err();
+        assert_eq!(error.span(), 3..6);
+        assert_eq!(error.suggestions(), ["Esc"]);
+        assert!(HotKey::from_vim("<C->").is_err());
+        assert!(HotKey::from_vim("ab").is_err());
+
+        for notation in [
+            "<C-S-a>",
+            "<M-Space>",
+            "<CR>",
+            "<C-->",
+            "<lt>",
+            "A",
+            "x",
+            "<D-F5>",
+        ] {
+            assert_eq!(parse(notation).to_vim().unwrap(), notation);
+        }
+        assert_eq!(HotKey::modifiers_only(Modifiers::CONTROL).to_vim(), None);
+        assert_eq!(
+            HotKey::new(Some(Modifiers::HYPER), Code::KeyA).to_vim(),
+            None
+        );
+
+        let sequence = HotKeySequence::from_vim("<C-x><C-f>").unwrap();
+        assert_eq!(sequence, HotKeySequence::from_emacs("C-x C-f").unwrap());
+        assert_eq!(sequence.to_vim().unwrap(), "<C-x><C-f>");
+        assert_eq!(HotKeySequence::from_vim("gg").unwrap().strokes().len(), 2);
+    }
+
+    #[test]
+    fn emacs_notation() {
+        let parse = |s| HotKey::from_emacs(s).unwrap();
+
+        assert_eq!(
+            parse("C-M-%"),
+            HotKey::from_trigger(Some(Modifiers::CONTROL | Modifiers::ALT), '%')
+        );
+        assert_eq!(
+            parse("s-k"),
+            HotKey::from_trigger(Some(Modifiers::SUPER), 'k')
+        );
+        assert_eq!(
+            parse("H-a"),
+            HotKey::from_trigger(Some(Modifiers::HYPER), 'a')
+        );
+        assert_eq!(
+            parse("M-x"),
+            HotKey::from_trigger(Some(Modifiers::ALT), 'x')
+        );
+        assert_eq!(parse("A-x"), parse("M-x"));
+        assert_eq!(parse("C-A"), parse("C-S-a"));
+        assert_eq!(parse("RET"), HotKey::new(None, Code::Enter));
+        assert_eq!(
+            parse("C-<f5>"),
+            HotKey::new(Some(Modifiers::CONTROL), Code::F5)
+        );
+        assert_eq!(
+            parse("M-SPC"),
+            HotKey::new(Some(Modifiers::ALT), Code::Space)
+        );
+        assert_eq!(
+            parse("C--"),
+            HotKey::from_trigger(Some(Modifiers::CONTROL), '-')
+        );
+        assert!(HotKey::from_emacs("C-<foo>").is_err());
+        assert!(HotKey::from_emacs("C-").is_err());
+
+        for notation in [
+            "C-M-%",
+            "s-k",
+            "C-H-M-S-s-a",
+            "RET",
+            "C-<f5>",
+            "C--",
+            "A",
+            "SPC",
+            "M-SPC",
+        ] {
+            assert_eq!(parse(notation).to_emacs().unwrap(), notation);
+        }
+        assert_eq!(parse("A-C-a").to_emacs().unwrap(), "C-M-a");
+        assert_eq!(
+            HotKey::new(Some(Modifiers::META), Code::KeyA).to_emacs(),
+            None
+        );
+        assert_eq!(HotKey::from_trigger(None, ' ').to_emacs(), None);
+
+        let error = HotKeySequence::from_emacs("C-x C-<f55>").unwrap_err();
+        assert_eq!(error.kind(), HotKeyParseErrorKind::UnsupportedKey);
+        assert_eq!(error.token(), "<f55>");
+
+        let sequence = HotKeySequence::from_emacs("C-x C-f").unwrap();
+        assert_eq!(sequence.to_emacs().unwrap(), "C-x C-f");
+        assert!(HotKeySequence::from_emacs(" ").is_err());
+    }
+}
@@ -0,0 +1,487 @@
+// Copyright 2022-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{
+    hash::Hash,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use super::{
+    fnv1a, parse_hotkey, HotKey, HotKeyParseError, HotKeyParseErrorKind, FNV_OFFSET_BASIS,
+};
+
+/// A multi-stroke hotkey, like `Ctrl+K Ctrl+C`, that is triggered when
+/// all of its strokes are pressed in order, each one within [`HotKeySequence::timeout`]
+/// of the previous one.
+///
+/// Only the first stroke is grabbed while the sequence is idle, the follow-up strokes
+/// are grabbed after the previous stroke has been pressed and released again once the
+/// sequence completes or times out.
+///
+/// Keys that aren't grabbed reach the focused application instead, so typing them between
+/// two strokes doesn't abort the sequence, only the timeout or another grabbed stroke,
+/// like the first stroke of another sequence, does.
+///
+/// Sequences with the same strokes are equal and share their id, whatever their timeout.
+///
+/// # Examples
+///
+/// ```
+/// # use global_hotkey::hotkey::{HotKey, HotKeySequence, Modifiers, Code};
+/// let sequence = HotKeySequence::new(vec![
+///     HotKey::new(Some(Modifiers::CONTROL), Code::KeyK),
+///     HotKey::new(Some(Modifiers::CONTROL), Code::KeyC),
+/// ]);
+/// let parsed: HotKeySequence = "Ctrl+KeyK Ctrl+KeyC".parse().unwrap();
+/// assert_eq!(sequence, parsed);
+/// ```
+#[derive(Debug, Clone)]
+pub struct HotKeySequence {
+    strokes: Vec<HotKey>,
+    timeout: Duration,
+    id: u32,
+}
+
+impl HotKeySequence {
+    /// The default maximum delay between two strokes of a sequence.
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(2);
+
+    /// Creates a new sequence from its strokes, in the order they have to be pressed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `strokes` is empty.
+    pub fn new(strokes: Vec<HotKey>) -> Self {
+        assert!(
+            !strokes.is_empty(),
+            "a hotkey sequence needs at least one stroke"
+        );
+
+        let id = Self::generate_hash(&strokes);
+
+        Self {
+            strokes,
+            timeout: Self::DEFAULT_TIMEOUT,
+            id,
+        }
+    }
+
+    /// Sets the maximum delay allowed between two strokes of this sequence.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Hashes the ids of the strokes with the same FNV-1a as [`HotKey::id`],
+    /// after a prefix that keeps a sequence from taking the id of a hotkey.
+    fn generate_hash(strokes: &[HotKey]) -> u32 {
+        let mut hash = fnv1a(FNV_OFFSET_BASIS, b"sequence");
+        for stroke in strokes {
+            hash = fnv1a(hash, &stroke.id().to_le_bytes());
+        }
+        hash
+    }
+
+    /// Returns the id associated with this sequence
+    /// which is a hash of the ids of its strokes.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the strokes of this sequence.
+    pub fn strokes(&self) -> &[HotKey] {
+        &self.strokes
+    }
+
+    /// Returns the maximum delay allowed between two strokes of this sequence.
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+}
+
+impl PartialEq for HotKeySequence {
+    fn eq(&self, other: &Self) -> bool {
+        self.strokes == other.strokes
+    }
+}
+
+impl Eq for HotKeySequence {}
+
+impl Hash for HotKeySequence {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.strokes.hash(state);
+    }
+}
+
+impl FromStr for HotKeySequence {
+    type Err = HotKeyParseError;
+    fn from_str(sequence: &str) -> Result<Self, Self::Err> {
+        parse_sequence(sequence)
+    }
+}
+
+/// Writes the canonical form of each stroke, see the `Display` implementation of [`HotKey`],
+/// separated by a space.
+impl std::fmt::Display for HotKeySequence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, stroke) in self.strokes().iter().enumerate() {
+            if i != 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{stroke}")?;
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<&str> for HotKeySequence {
+    type Error = HotKeyParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        parse_sequence(value)
+    }
+}
+
+impl TryFrom<String> for HotKeySequence {
+    type Error = HotKeyParseError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        parse_sequence(&value)
+    }
+}
+
+fn parse_sequence(sequence: &str) -> Result<HotKeySequence, HotKeyParseError> {
+    let mut strokes = Vec::new();
+    for stroke in split_strokes(sequence) {
+        // strokes can be separated by a comma, "Ctrl+K, Ctrl+C",
+        // but a comma right after a `+` is the comma key itself, "Ctrl+,"
+        let stroke = match stroke.strip_suffix(',') {
+            Some(s) if !s.is_empty() && !s.trim_end().ends_with('+') => s,
+            _ => stroke,
+        };
+        strokes.push(parse_hotkey(stroke).map_err(|e| e.within(sequence, stroke))?);
+    }
+
+    if strokes.is_empty() {
+        let kind = HotKeyParseErrorKind::EmptyToken;
+        return Err(HotKeyParseError::new(kind, sequence, sequence.trim()));
+    }
+
+    Ok(HotKeySequence::new(strokes))
+}
+
+/// Splits a sequence on whitespace, except around a `+` so "Ctrl + K" stays one stroke.
+///
+/// The strokes are slices of `sequence` so parse errors can point into it.
+fn split_strokes(sequence: &str) -> Vec<&str> {
+    let mut strokes = Vec::new();
+    let mut start = None;
+    // the last two characters of the current stroke, besides whitespace
+    let mut tail = ['\0'; 2];
+    let mut chars = sequence.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if !c.is_whitespace() {
+            start.get_or_insert(i);
+            tail = [tail[1], c];
+            continue;
+        }
+
+        while chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+        let Some(stroke_start) = start else {
+            continue;
+        };
+        // a trailing `++` is the plus key, which ends the stroke
+        let after_plus = tail[1] == '+' && tail[0] != '+';
+        if after_plus || matches!(chars.peek(), Some((_, '+'))) {
+            continue;
+        }
+        strokes.push(&sequence[stroke_start..i]);
+        start = None;
+        tail = ['\0'; 2];
+    }
+    if let Some(stroke_start) = start {
+        strokes.push(sequence[stroke_start..].trim_end());
+    }
+    strokes
+}
+
+/// Tracks the progress of registered [`HotKeySequence`]s.
+///
+/// Platform implementations feed it the strokes they receive and keep the strokes
+/// returned by [`SequenceTracker::grabbed_strokes`] grabbed.
+#[derive(Debug, Default)]
+pub(crate) struct SequenceTracker {
+    sequences: Vec<HotKeySequence>,
+    pending: Option<Pending>,
+    /// The final stroke of a completed sequence, kept until it is released.
+    held: Option<(u32, u32)>,
+}
+
+#[derive(Debug)]
+struct Pending {
+    candidates: Vec<usize>,
+    progress: usize,
+    last_stroke: Instant,
+}
+
+impl SequenceTracker {
+    pub fn contains(&self, id: u32) -> bool {
+        self.sequences.iter().any(|s| s.id() == id)
+    }
+
+    /// Adds a sequence, returns `false` if it was already registered.
+    pub fn register(&mut self, sequence: HotKeySequence) -> bool {
+        if self.contains(sequence.id()) {
+            return false;
+        }
+        self.reset();
+        self.sequences.push(sequence);
+        true
+    }
+
+    pub fn unregister(&mut self, id: u32) -> Option<HotKeySequence> {
+        let index = self.sequences.iter().position(|s| s.id() == id)?;
+        self.reset();
+        Some(self.sequences.remove(index))
+    }
+
+    fn reset(&mut self) {
+        self.pending = None;
+        self.held = None;
+    }
+
+    /// Strokes that should currently be grabbed: the first stroke of every sequence
+    /// and the follow-up strokes of the sequences in progress.
+    pub fn grabbed_strokes(&self) -> Vec<HotKey> {
+        let mut strokes: Vec<HotKey> = Vec::new();
+        let mut push = |stroke: HotKey| {
+            if !strokes.contains(&stroke) {
+                strokes.push(stroke);
+            }
+        };
+
+        for sequence in &self.sequences {
+            push(sequence.strokes[0]);
+        }
+        if let Some(pending) = &self.pending {
+            for &i in &pending.candidates {
+                push(self.sequences[i].strokes[pending.progress]);
+            }
+        }
+        if let Some((stroke, sequence)) = self.held {
+            if let Some(sequence) = self.sequences.iter().find(|s| s.id() == sequence) {
+                if let Some(stroke) = sequence.strokes.iter().find(|s| s.id() == stroke) {
+                    push(*stroke);
+                }
+            }
+        }
+
+        strokes
+    }
+
+    /// Returns the instant after which [`SequenceTracker::expire`] will abort the sequences in progress.
+    pub fn deadline(&self) -> Option<Instant> {
+        let pending = self.pending.as_ref()?;
+        pending
+            .candidates
+            .iter()
+            .map(|&i| pending.last_stroke + self.sequences[i].timeout)
+            .max()
+    }
+
+    /// Aborts the sequences in progress whose timeout elapsed,
+    /// returns `true` if that changed the grabbed strokes.
+    pub fn expire(&mut self, now: Instant) -> bool {
+        let Some(pending) = &mut self.pending else {
+            return false;
+        };
+
+        let sequences = &self.sequences;
+        let before = pending.candidates.len();
+        pending
+            .candidates
+            .retain(|&i| now.duration_since(pending.last_stroke) <= sequences[i].timeout);
+
+        if pending.candidates.is_empty() {
+            self.pending = None;
+            return true;
+        }
+        before != pending.candidates.len()
+    }
+
+    /// Feeds a pressed stroke, returns the id of the sequence it completed, if any.
+    pub fn press(&mut self, stroke: u32, now: Instant) -> Option<u32> {
+        self.expire(now);
+
+        if let Some(pending) = self.pending.take() {
+            let candidates: Vec<usize> = pending
+                .candidates
+                .into_iter()
+                .filter(|&i| self.sequences[i].strokes[pending.progress].id() == stroke)
+                .collect();
+            if !candidates.is_empty() {
+                return self.advance(candidates, pending.progress + 1, stroke, now);
+            }
+        }
+
+        let candidates: Vec<usize> = (0..self.sequences.len())
+            .filter(|&i| self.sequences[i].strokes[0].id() == stroke)
+            .collect();
+        if !candidates.is_empty() {
+            return self.advance(candidates, 1, stroke, now);
+        }
+
+        None
+    }
+
+    fn advance(
+        &mut self,
+        candidates: Vec<usize>,
+        progress: usize,
+        stroke: u32,
+        now: Instant,
+    ) -> Option<u32> {
+        if let Some(&i) = candidates
+            .iter()
+            .find(|&&i| self.sequences[i].strokes.len() == progress)
+        {
+            let id = self.sequences[i].id();
+            self.pending = None;
+            self.held = Some((stroke, id));
+            return Some(id);
+        }
+
+        self.pending = Some(Pending {
+            candidates,
+            progress,
+            last_stroke: now,
+        });
+        None
+    }
+
+    /// Feeds a released stroke, returns the id of the sequence
+    /// whose final stroke has been released, if any.
+    pub fn release(&mut self, stroke: u32) -> Option<u32> {
+        match self.held {
+            Some((held, id)) if held == stroke => {
+                self.held = None;
+                Some(id)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::{HotKeySequence, SequenceTracker};
+    use crate::hotkey::HotKey;
+
+    fn hotkey(s: &str) -> HotKey {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parse_sequence() {
+        let expected = HotKeySequence::new(vec![hotkey("Ctrl+k"), hotkey("Ctrl+c")]);
+        assert_eq!("Ctrl+K Ctrl+C".parse::<HotKeySequence>().unwrap(), expected);
+        assert_eq!(
+            "Ctrl+K, Ctrl+C".parse::<HotKeySequence>().unwrap(),
+            expected
+        );
+        assert_eq!(
+            "Ctrl + K  Ctrl + C".parse::<HotKeySequence>().unwrap(),
+            expected
+        );
+
+        let comma = HotKeySequence::new(vec![hotkey("Ctrl+k"), hotkey("Ctrl+,")]);
+        assert_eq!("Ctrl+K, Ctrl+,".parse::<HotKeySequence>().unwrap(), comma);
+        assert_eq!(comma.to_string(), "Control+k Control+,");
+        assert_eq!(comma.to_string().parse::<HotKeySequence>().unwrap(), comma);
+
+        let plus = HotKeySequence::new(vec![hotkey("Ctrl++"), hotkey("Ctrl+k")]);
+        assert_eq!("Ctrl++ Ctrl+K".parse::<HotKeySequence>().unwrap(), plus);
+        assert_eq!("Ctrl + + Ctrl+K".parse::<HotKeySequence>().unwrap(), plus);
+
+        assert!("".parse::<HotKeySequence>().is_err());
+        let error = "Ctrl+K  Ctrl + Foo".parse::<HotKeySequence>().unwrap_err();
+        assert_eq!(error.input(), "Ctrl+K  Ctrl + Foo");
+        assert_eq!(error.span(), 15..18);
+    }
+
+    #[test]
+    fn sequence_id() {
+        let s1: HotKeySequence = "Ctrl+K Ctrl+C".parse().unwrap();
+        let s2: HotKeySequence = "Ctrl+K Ctrl+U".parse().unwrap();
+        let s3 = s1.clone().with_timeout(Duration::from_millis(10));
+        assert_ne!(s1.id(), s2.id());
+        assert_eq!(s1.id(), s3.id());
+        assert_eq!(s1, s3);
+        assert_ne!(s1.id(), hotkey("Ctrl+K").id());
+    }
+
+    #[test]
+    fn tracker_completes_and_grabs_follow_ups() {
+        let (k, c, u) = (hotkey("Ctrl+K"), hotkey("Ctrl+C"), hotkey("Ctrl+U"));
+        let comment: HotKeySequence = "Ctrl+K Ctrl+C".parse().unwrap();
+        let uncomment: HotKeySequence = "Ctrl+K Ctrl+U".parse().unwrap();
+        let x = hotkey("Ctrl+X");
+        let cut = HotKeySequence::new(vec![x, hotkey("Ctrl+Y")]);
+
+        let mut tracker = SequenceTracker::default();
+        assert!(tracker.register(comment.clone()));
+        assert!(tracker.register(uncomment.clone()));
+        assert!(tracker.register(cut));
+        assert!(!tracker.register(comment.clone()));
+        assert_eq!(tracker.grabbed_strokes(), vec![k, x]);
+
+        let now = Instant::now();
+        assert_eq!(tracker.press(k.id(), now), None);
+        assert_eq!(tracker.grabbed_strokes(), vec![k, x, c, u]);
+
+        assert_eq!(tracker.press(u.id(), now), Some(uncomment.id()));
+        assert_eq!(tracker.grabbed_strokes(), vec![k, x, u]);
+        assert_eq!(tracker.release(c.id()), None);
+        assert_eq!(tracker.release(u.id()), Some(uncomment.id()));
+        assert_eq!(tracker.grabbed_strokes(), vec![k, x]);
+
+        // only grabbed strokes are fed to the tracker, the first stroke
+        // of another sequence aborts the sequence in progress
+        assert_eq!(tracker.press(k.id(), now), None);
+        assert_eq!(tracker.press(x.id(), now), None);
+        assert_eq!(tracker.press(c.id(), now), None);
+        assert_eq!(tracker.grabbed_strokes(), vec![k, x]);
+    }
+
+    #[test]
+    fn tracker_times_out() {
+        let (k, c) = (hotkey("Ctrl+K"), hotkey("Ctrl+C"));
+        let sequence = HotKeySequence::new(vec![k, c]).with_timeout(Duration::from_millis(500));
+
+        let mut tracker = SequenceTracker::default();
+        tracker.register(sequence.clone());
+
+        let start = Instant::now();
+        tracker.press(k.id(), start);
+        assert_eq!(tracker.deadline(), Some(start + Duration::from_millis(500)));
+        assert!(!tracker.expire(start + Duration::from_millis(400)));
+        assert_eq!(
+            tracker.press(c.id(), start + Duration::from_millis(400)),
+            Some(sequence.id())
+        );
+
+        tracker.release(c.id());
+        tracker.press(k.id(), start);
+        assert!(tracker.expire(start + Duration::from_millis(600)));
+        assert_eq!(tracker.deadline(), None);
+        assert_eq!(tracker.grabbed_strokes(), vec![k]);
+        assert_eq!(
+            tracker.press(c.id(), start + Duration::from_millis(600)),
+            None
+        );
+    }
+}
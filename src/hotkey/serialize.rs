@@ -0,0 +1,153 @@
+// Copyright 2022-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use serde::{
+    de::{self, value::MapAccessDeserializer},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use super::{
+    parse_hotkey, parse_trigger, HotKey, HotKeyParseError, HotKeyParseErrorKind, ModifierSides,
+    Modifiers, Trigger, KEY_NAMES,
+};
+
+/// Serializes as the canonical string of the hotkey, like `Shift+Control+KeyK`,
+/// see the `Display` implementation of [`HotKey`].
+impl Serialize for HotKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Deserializes a hotkey string, like `"Ctrl+Shift+K"`,
+/// or a structured hotkey, like `{ "mods": "CONTROL | SHIFT", "key": "KeyK" }`.
+///
+/// The `key` of a structured hotkey is written like the main key of a hotkey string,
+/// it can be left out for a modifier-only hotkey. The optional `sides` restrict the modifiers
+/// to their left or right key, like `"CONTROL_LEFT"`.
+///
+/// Formats that aren't human-readable, like bincode, only hold the canonical string.
+impl<'de> Deserialize<'de> for HotKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(HotKeyVisitor)
+        } else {
+            // formats that don't describe themselves can't tell a string from a map
+            deserializer.deserialize_str(HotKeyVisitor)
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StructuredHotKey {
+    #[serde(default)]
+    mods: Modifiers,
+    #[serde(default)]
+    sides: ModifierSides,
+    key: Option<String>,
+}
+
+struct HotKeyVisitor;
+
+impl<'de> de::Visitor<'de> for HotKeyVisitor {
+    type Value = HotKey;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("a hotkey string or a map with `mods` and `key`")
+    }
+
+    fn visit_str<E: de::Error>(self, hotkey: &str) -> Result<HotKey, E> {
+        parse_hotkey(hotkey).map_err(E::custom)
+    }
+
+    fn visit_map<A: de::MapAccess<'de>>(self, map: A) -> Result<HotKey, A::Error> {
+        let hotkey = StructuredHotKey::deserialize(MapAccessDeserializer::new(map))?;
+        let trigger = match hotkey.key.as_deref() {
+            None => Trigger::ModifiersOnly,
+            Some(key) => parse_trigger(key).ok_or_else(|| {
+                let kind = HotKeyParseErrorKind::UnsupportedKey;
+                let error =
+                    HotKeyParseError::new(kind, key, key).suggest(KEY_NAMES.iter().copied());
+                de::Error::custom(error)
+            })?,
+        };
+        Ok(HotKey::from_trigger(Some(hotkey.mods), trigger).with_sides(hotkey.sides))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use keyboard_types::Code;
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+    use crate::{hotkey::WheelDirection, GlobalHotKeyEvent, HotKeyState};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Binding {
+        hotkey: HotKey,
+    }
+
+    #[test]
+    fn hotkey_as_string() {
+        let binding = Binding {
+            hotkey: HotKey::new(Some(Modifiers::CONTROL | Modifiers::SHIFT), Code::KeyK),
+        };
+        let written = toml::to_string(&binding).unwrap();
+        assert_eq!(written.trim(), r#"hotkey = "Shift+Control+KeyK""#);
+        assert_eq!(toml::from_str::<Binding>(&written).unwrap(), binding);
+
+        let binding: Binding = toml::from_str(r#"hotkey = "Ctrl+Shift+K""#).unwrap();
+        assert_eq!(binding.hotkey, "Shift+Control+K".parse().unwrap());
+
+        let error = toml::from_str::<Binding>(r#"hotkey = "Ctrl+Shft+K""#).unwrap_err();
+        assert!(error.to_string().contains("did you mean \"Shift\""));
+    }
+
+    #[test]
+    fn hotkey_as_map() {
+        let binding: Binding =
+            toml::from_str(r#"hotkey = { mods = "CONTROL | SHIFT", key = "KeyK" }"#).unwrap();
+        assert_eq!(binding.hotkey, "Ctrl+Shift+KeyK".parse().unwrap());
+
+        let binding: Binding = toml::from_str(
+            r#"hotkey = { mods = "CONTROL", sides = "CONTROL_RIGHT", key = "Mouse4" }"#,
+        )
+        .unwrap();
+        assert_eq!(binding.hotkey, "RCtrl+Mouse4".parse().unwrap());
+
+        let binding: Binding = toml::from_str(r#"hotkey = { mods = "ALT" }"#).unwrap();
+        assert_eq!(binding.hotkey, HotKey::modifiers_only(Modifiers::ALT));
+
+        assert!(toml::from_str::<Binding>(r#"hotkey = { key = "Foo" }"#).is_err());
+        assert!(toml::from_str::<Binding>(r#"hotkey = { mod = "ALT", key = "K" }"#).is_err());
+    }
+
+    #[test]
+    fn hotkey_as_bytes() {
+        let binding = Binding {
+            hotkey: "RCtrl+Shift+Mouse4".parse().unwrap(),
+        };
+        let written = bincode::serialize(&binding).unwrap();
+        assert_eq!(bincode::deserialize::<Binding>(&written).unwrap(), binding);
+    }
+
+    #[test]
+    fn event_round_trip() {
+        let events = [
+            GlobalHotKeyEvent::new(7, HotKeyState::Pressed),
+            GlobalHotKeyEvent::new(7, HotKeyState::Repeat(3)),
+            GlobalHotKeyEvent::new(7, HotKeyState::Scrolled(WheelDirection::Up)),
+        ];
+        for event in events {
+            let written = toml::to_string(&event).unwrap();
+            assert!(written.contains("id = 7"), "{written}");
+            assert_eq!(
+                toml::from_str::<GlobalHotKeyEvent>(&written).unwrap(),
+                event
+            );
+        }
+    }
+}
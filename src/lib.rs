@@ -50,32 +50,58 @@
 //! - macOS
 //! - Linux (X11 Only)
 
+use std::sync::{Arc, Weak};
+
 use crossbeam_channel::{unbounded, Receiver, Sender};
 use once_cell::sync::{Lazy, OnceCell};
 
+mod activation;
 mod error;
 pub mod hotkey;
 mod platform_impl;
 
+use self::activation::Activations;
+pub use self::activation::{Activation, HotKeyOptions};
 pub use self::error::*;
-use hotkey::HotKey;
+use hotkey::{HotKey, HotKeySequence};
 
 /// Describes the state of the [`HotKey`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum HotKeyState {
     /// The [`HotKey`] is pressed (the key is down).
     Pressed,
     /// The [`HotKey`] is released (the key is up).
     Released,
+    /// The [`HotKey`] registered with [`Activation::Hold`] is still held,
+    /// for the given duration so far.
+    Holding(std::time::Duration),
+    /// The [`HotKey`] registered with [`Activation::Hold`] has been released
+    /// before it was held long enough.
+    Cancelled,
+    /// The [`HotKey`] registered with [`HotKeyOptions::with_repeat`] is still held
+    /// and has been repeated by the OS, for the given number of times since it was pressed.
+    Repeat(u32),
+    /// The scroll wheel of a [`HotKey`] bound to [`Trigger::Wheel`](hotkey::Trigger::Wheel)
+    /// has been scrolled by one notch in this direction.
+    Scrolled(hotkey::WheelDirection),
 }
 
 /// Describes a global hotkey event emitted when a [`HotKey`] is pressed or released.
+///
+/// Events of a [`HotKeySequence`] are emitted with the id of the sequence
+/// when its final stroke is pressed or released.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GlobalHotKeyEvent {
-    /// Id of the associated [`HotKey`].
+    /// Id of the associated [`HotKey`] or [`HotKeySequence`].
     pub id: u32,
     /// State of the associated [`HotKey`].
     pub state: HotKeyState,
+    /// Number of consecutive taps that triggered this event, see [`Activation::Taps`].
+    ///
+    /// This is `1` for hotkeys registered without taps.
+    pub tap_count: u32,
 }
 
 /// A reciever that could be used to listen to global hotkey events.
@@ -87,15 +113,26 @@ static GLOBAL_HOTKEY_CHANNEL: Lazy<(Sender<GlobalHotKeyEvent>, GlobalHotKeyEvent
 static GLOBAL_HOTKEY_EVENT_HANDLER: OnceCell<Option<GlobalHotKeyEventHandler>> = OnceCell::new();
 
 impl GlobalHotKeyEvent {
+    pub(crate) fn new(id: u32, state: HotKeyState) -> Self {
+        Self {
+            id,
+            state,
+            tap_count: 1,
+        }
+    }
+
     /// Returns the id of the associated [`HotKey`].
     pub fn id(&self) -> u32 {
         self.id
     }
     /// Returns the state of the associated [`HotKey`].
-
     pub fn state(&self) -> HotKeyState {
         self.state
     }
+    /// Returns the number of consecutive taps that triggered this event.
+    pub fn tap_count(&self) -> u32 {
+        self.tap_count
+    }
 
     /// Gets a reference to the event channel's [`GlobalHotKeyEventReceiver`]
     /// which can be used to listen for global hotkey events.
@@ -121,7 +158,7 @@ impl GlobalHotKeyEvent {
         }
     }
 
-    pub(crate) fn send(event: GlobalHotKeyEvent) {
+    pub(crate) fn emit(event: GlobalHotKeyEvent) {
         if let Some(handler) = GLOBAL_HOTKEY_EVENT_HANDLER.get_or_init(|| None) {
             handler(event);
         } else {
@@ -131,13 +168,24 @@ impl GlobalHotKeyEvent {
 }
 
 pub struct GlobalHotKeyManager {
-    platform_impl: platform_impl::GlobalHotKeyManager,
+    platform_impl: Arc<platform_impl::GlobalHotKeyManager>,
+    activations: Arc<Activations>,
+}
+
+impl Drop for GlobalHotKeyManager {
+    fn drop(&mut self) {
+        self.activations.clear();
+    }
 }
 
 impl GlobalHotKeyManager {
     pub fn new() -> crate::Result<Self> {
+        let activations = Arc::new(Activations::default());
         Ok(Self {
-            platform_impl: platform_impl::GlobalHotKeyManager::new()?,
+            platform_impl: Arc::new(platform_impl::GlobalHotKeyManager::new(
+                activations.clone(),
+            )?),
+            activations,
         })
     }
 
@@ -145,20 +193,239 @@ impl GlobalHotKeyManager {
         self.platform_impl.register(hotkey)
     }
 
+    /// Registers a [`HotKey`] until the returned [`ScopedHotKey`] is dropped.
+    ///
+    /// ```no_run
+    /// use global_hotkey::{GlobalHotKeyManager, hotkey::{HotKey, Modifiers, Code}};
+    ///
+    /// let manager = GlobalHotKeyManager::new().unwrap();
+    /// {
+    ///     let _guard = manager.register_scoped(HotKey::new(Some(Modifiers::SHIFT), Code::KeyD));
+    ///     // Shift+D is registered here
+    /// }
+    /// // and unregistered here
+    /// ```
+    pub fn register_scoped(&self, hotkey: HotKey) -> crate::Result<ScopedHotKey> {
+        self.register(hotkey)?;
+        Ok(ScopedHotKey {
+            hotkey,
+            manager: Arc::downgrade(&self.platform_impl),
+            activations: Arc::downgrade(&self.activations),
+        })
+    }
+
+    /// Registers a [`HotKey`] whose events are emitted according to `options`,
+    /// for example only after it has been tapped twice.
+    pub fn register_with_options(
+        &self,
+        hotkey: HotKey,
+        options: HotKeyOptions,
+    ) -> crate::Result<()> {
+        self.platform_impl.register(hotkey)?;
+        self.activations.set_options(hotkey.id(), options);
+        Ok(())
+    }
+
+    /// Unregisters a hotkey, which fails with [`FailureReason::NotRegistered`] on every platform
+    /// if it isn't registered.
     pub fn unregister(&self, hotkey: HotKey) -> crate::Result<()> {
-        self.platform_impl.unregister(hotkey)
+        self.platform_impl.unregister(hotkey)?;
+        self.activations.remove_options(hotkey.id());
+        Ok(())
+    }
+
+    /// Replaces the registered hotkey `old` with `new`, keeping its [`HotKeyOptions`].
+    ///
+    /// `new` is registered before `old` is released, and when anything fails `old` stays registered,
+    /// so a failed rebind never leaves neither of them registered.
+    ///
+    /// ```no_run
+    /// use global_hotkey::{GlobalHotKeyManager, hotkey::HotKey};
+    ///
+    /// let manager = GlobalHotKeyManager::new().unwrap();
+    /// let old: HotKey = "Ctrl+Alt+P".parse().unwrap();
+    /// manager.register(old).unwrap();
+    ///
+    /// if let Err(e) = manager.rebind(old, "Ctrl+Alt+O".parse().unwrap()) {
+    ///     eprintln!("Ctrl+Alt+O is unavailable, keeping Ctrl+Alt+P: {e}");
+    /// }
+    /// ```
+    pub fn rebind(&self, old: HotKey, new: HotKey) -> crate::Result<()> {
+        if old == new && !self.is_registered(&old) {
+            return Err(crate::Error::FailedToUnRegister {
+                hotkey: old,
+                reason: FailureReason::NotRegistered,
+            });
+        } else if old == new {
+            return Ok(());
+        }
+
+        self.platform_impl.rebind(old, new)?;
+        self.activations.move_options(old.id(), new.id());
+        Ok(())
     }
 
+    /// Registers all of `hotkeys`, or none of them.
+    ///
+    /// When one of them fails, the ones registered before it are unregistered again
+    /// and its error is returned. See [`GlobalHotKeyManager::register_each`]
+    /// to register as many as possible instead.
     pub fn register_all(&self, hotkeys: &[HotKey]) -> crate::Result<()> {
         self.platform_impl.register_all(hotkeys)?;
         Ok(())
     }
 
+    /// Registers each of `hotkeys` on a best-effort basis, and returns the result of each one,
+    /// in the order of `hotkeys`.
+    ///
+    /// A hotkey that fails to register doesn't prevent the others from being registered.
+    ///
+    /// ```no_run
+    /// use global_hotkey::{GlobalHotKeyManager, hotkey::HotKey};
+    ///
+    /// let manager = GlobalHotKeyManager::new().unwrap();
+    /// let hotkeys: Vec<HotKey> = ["Ctrl+Shift+K", "Alt+F4"].map(|h| h.parse().unwrap()).into();
+    /// for (hotkey, result) in manager.register_each(&hotkeys) {
+    ///     if let Err(e) = result {
+    ///         eprintln!("{hotkey} is unavailable: {e}");
+    ///     }
+    /// }
+    /// ```
+    pub fn register_each(&self, hotkeys: &[HotKey]) -> Vec<(HotKey, crate::Result<()>)> {
+        self.platform_impl.register_each(hotkeys)
+    }
+
     pub fn unregister_all(&self, hotkeys: &[HotKey]) -> crate::Result<()> {
-        self.platform_impl.unregister_all(hotkeys)?;
+        let result = self.platform_impl.unregister_all(hotkeys);
+        // the hotkeys that failed to unregister keep their options
+        let registered = self.hotkeys();
+        for hotkey in hotkeys.iter().filter(|h| !registered.contains(h)) {
+            self.activations.remove_options(hotkey.id());
+        }
+        result
+    }
+
+    /// Registers a multi-stroke [`HotKeySequence`].
+    ///
+    /// Only the first stroke is grabbed until it is pressed, the follow-up strokes
+    /// are then grabbed until the sequence completes or times out.
+    pub fn register_sequence(&self, sequence: HotKeySequence) -> crate::Result<()> {
+        self.platform_impl.register_sequence(sequence)
+    }
+
+    /// Unregisters a [`HotKeySequence`], which fails with [`FailureReason::NotRegistered`],
+    /// carrying its first stroke, if it isn't registered.
+    pub fn unregister_sequence(&self, sequence: &HotKeySequence) -> crate::Result<()> {
+        self.platform_impl.unregister_sequence(sequence)
+    }
+
+    /// Enables or disables a registered [`HotKey`] without unregistering it.
+    ///
+    /// A disabled hotkey is released, so its key combination reaches other applications,
+    /// and is grabbed again once enabled. Enabling fails when the combination
+    /// can no longer be grabbed, the hotkey then stays disabled.
+    ///
+    /// Disabling a held hotkey releases it right away, with [`HotKeyState::Released`],
+    /// or [`HotKeyState::Cancelled`] for a hold that didn't fire yet.
+    pub fn set_enabled(&self, hotkey: &HotKey, enabled: bool) -> crate::Result<()> {
+        self.platform_impl.set_enabled(*hotkey, enabled)?;
+        if !enabled {
+            self.activations.park(hotkey.id());
+        }
         Ok(())
     }
+
+    /// Releases every registered hotkey and sequence until [`GlobalHotKeyManager::resume_all`],
+    /// like while recording a new hotkey or during a presentation.
+    ///
+    /// The hotkeys stay registered, and those registered while paused are only grabbed on resume.
+    /// Registering one while paused still grabs and releases it right away, so a combination
+    /// that can't be grabbed fails to register rather than to resume.
+    /// The held hotkeys are released right away, like with [`GlobalHotKeyManager::set_enabled`].
+    ///
+    /// ```no_run
+    /// use global_hotkey::GlobalHotKeyManager;
+    ///
+    /// let manager = GlobalHotKeyManager::new().unwrap();
+    /// manager.pause_all().unwrap();
+    /// // record a hotkey, the registered ones reach the recorder like any other keys
+    /// manager.resume_all().unwrap();
+    /// ```
+    pub fn pause_all(&self) -> crate::Result<()> {
+        let result = self.platform_impl.pause_all();
+        // every grab is released even when some of them fail
+        self.activations.park_all();
+        result
+    }
+
+    /// Grabs the hotkeys and sequences released by [`GlobalHotKeyManager::pause_all`] again,
+    /// except the disabled ones.
+    ///
+    /// Every hotkey is grabbed again even when some of them fail, those are then disabled
+    /// and the first error is returned, see [`GlobalHotKeyManager::set_enabled`] to retry them.
+    pub fn resume_all(&self) -> crate::Result<()> {
+        self.platform_impl.resume_all()
+    }
+
+    /// Returns whether `hotkey` is currently registered with this manager.
+    pub fn is_registered(&self, hotkey: &HotKey) -> bool {
+        self.hotkey_for_id(hotkey.id()).is_some()
+    }
+
+    /// Returns the hotkeys currently registered with this manager, in no particular order,
+    /// including the disabled ones and the ones released by [`GlobalHotKeyManager::pause_all`].
+    ///
+    /// The strokes of registered [`HotKeySequence`]s are not included.
+    pub fn hotkeys(&self) -> Vec<HotKey> {
+        self.platform_impl.hotkeys()
+    }
+
+    /// Returns the registered hotkey with this id, like the one a [`GlobalHotKeyEvent`] is for.
+    pub fn hotkey_for_id(&self, id: u32) -> Option<HotKey> {
+        self.hotkeys().into_iter().find(|hotkey| hotkey.id() == id)
+    }
 }
+
+/// A [`HotKey`] registered with [`GlobalHotKeyManager::register_scoped`],
+/// which is unregistered when the guard is dropped.
+///
+/// The guard doesn't keep the manager alive. When the manager is dropped first,
+/// its hotkeys are released with it and dropping the guard does nothing.
+#[must_use = "dropping the guard unregisters the hotkey"]
+pub struct ScopedHotKey {
+    hotkey: HotKey,
+    manager: Weak<platform_impl::GlobalHotKeyManager>,
+    activations: Weak<Activations>,
+}
+
+impl ScopedHotKey {
+    /// Returns the registered [`HotKey`].
+    pub fn hotkey(&self) -> HotKey {
+        self.hotkey
+    }
+}
+
+impl std::fmt::Debug for ScopedHotKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ScopedHotKey")
+            .field("hotkey", &self.hotkey)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for ScopedHotKey {
+    fn drop(&mut self) {
+        let Some(platform_impl) = self.manager.upgrade() else {
+            return;
+        };
+        if platform_impl.unregister(self.hotkey).is_ok() {
+            if let Some(activations) = self.activations.upgrade() {
+                activations.remove_options(self.hotkey.id());
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     fn assert_send<T: Send>() {}
@@ -168,5 +435,33 @@ mod tests {
     fn is_send_sync() {
         assert_send::<super::GlobalHotKeyManager>();
         assert_sync::<super::GlobalHotKeyManager>();
+        assert_send::<super::ScopedHotKey>();
+        assert_sync::<super::ScopedHotKey>();
+    }
+
+    #[test]
+    fn lookup_by_id() {
+        use super::GlobalHotKeyManager;
+        use crate::hotkey::{Code, HotKey, Modifiers};
+
+        let manager = GlobalHotKeyManager::new().unwrap();
+        let hotkey = HotKey::new(Some(Modifiers::CONTROL | Modifiers::SHIFT), Code::F24);
+        let other = HotKey::new(Some(Modifiers::CONTROL | Modifiers::SHIFT), Code::F23);
+        // registering fails without a display server, the lookup follows the result either way
+        let registered = manager.register(hotkey).is_ok();
+
+        assert_eq!(
+            manager.hotkey_for_id(hotkey.id()),
+            registered.then_some(hotkey)
+        );
+        assert_eq!(manager.is_registered(&hotkey), registered);
+        assert_eq!(manager.hotkey_for_id(other.id()), None);
+        assert!(!manager.is_registered(&other));
+
+        if registered {
+            manager.unregister(hotkey).unwrap();
+            assert_eq!(manager.hotkey_for_id(hotkey.id()), None);
+            assert!(!manager.is_registered(&hotkey));
+        }
     }
 }
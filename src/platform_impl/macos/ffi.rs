@@ -68,6 +68,7 @@ pub type _bindgen_ty_1981 = ::std::os::raw::c_uint;
 pub const kEventHotKeyReleased: _bindgen_ty_1981 = 6;
 pub type _bindgen_ty_1 = ::std::os::raw::c_uint;
 pub const noErr: _bindgen_ty_1 = 0;
+pub const eventHotKeyExistsErr: OSStatus = -9878;
 
 #[repr(C, packed(2))]
 #[derive(Debug, Copy, Clone)]
@@ -115,3 +116,98 @@ extern "C" {
     ) -> OSStatus;
     pub fn UnregisterEventHotKey(inHotKey: EventHotKeyRef) -> OSStatus;
 }
+
+pub type CFIndex = ::std::os::raw::c_long;
+pub type CFOptionFlags = ::std::os::raw::c_ulong;
+pub type CFTimeInterval = f64;
+pub type CFAbsoluteTime = CFTimeInterval;
+pub type CFAllocatorRef = *const ::std::os::raw::c_void;
+pub type CFStringRef = *const ::std::os::raw::c_void;
+pub type CFRunLoopRef = *mut ::std::os::raw::c_void;
+pub type CFRunLoopTimerRef = *mut ::std::os::raw::c_void;
+pub type CFRunLoopTimerCallBack = ::std::option::Option<
+    unsafe extern "C" fn(timer: CFRunLoopTimerRef, info: *mut ::std::os::raw::c_void),
+>;
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct CFRunLoopTimerContext {
+    pub version: CFIndex,
+    pub info: *mut ::std::os::raw::c_void,
+    pub retain: *const ::std::os::raw::c_void,
+    pub release: *const ::std::os::raw::c_void,
+    pub copyDescription: *const ::std::os::raw::c_void,
+}
+
+#[link(name = "CoreFoundation", kind = "framework")]
+extern "C" {
+    pub static kCFRunLoopCommonModes: CFStringRef;
+    pub fn CFAbsoluteTimeGetCurrent() -> CFAbsoluteTime;
+    pub fn CFRunLoopGetMain() -> CFRunLoopRef;
+    pub fn CFRunLoopTimerCreate(
+        allocator: CFAllocatorRef,
+        fireDate: CFAbsoluteTime,
+        interval: CFTimeInterval,
+        flags: CFOptionFlags,
+        order: CFIndex,
+        callout: CFRunLoopTimerCallBack,
+        context: *mut CFRunLoopTimerContext,
+    ) -> CFRunLoopTimerRef;
+    pub fn CFRunLoopAddTimer(rl: CFRunLoopRef, timer: CFRunLoopTimerRef, mode: CFStringRef);
+    pub fn CFRunLoopTimerInvalidate(timer: CFRunLoopTimerRef);
+    pub fn CFRelease(cf: *const ::std::os::raw::c_void);
+}
+
+pub type CGEventSourceStateID = i32;
+pub type CGEventFlags = u64;
+pub const kCGEventSourceStateHIDSystemState: CGEventSourceStateID = 1;
+
+// device dependent modifier flags, from IOKit/hidsystem/IOLLEvent.h
+pub const NX_DEVICELCTLKEYMASK: CGEventFlags = 0x00000001;
+pub const NX_DEVICELSHFTKEYMASK: CGEventFlags = 0x00000002;
+pub const NX_DEVICERSHFTKEYMASK: CGEventFlags = 0x00000004;
+pub const NX_DEVICELCMDKEYMASK: CGEventFlags = 0x00000008;
+pub const NX_DEVICERCMDKEYMASK: CGEventFlags = 0x00000010;
+pub const NX_DEVICELALTKEYMASK: CGEventFlags = 0x00000020;
+pub const NX_DEVICERALTKEYMASK: CGEventFlags = 0x00000040;
+pub const NX_DEVICERCTLKEYMASK: CGEventFlags = 0x00002000;
+
+#[link(name = "CoreGraphics", kind = "framework")]
+extern "C" {
+    pub fn CGEventSourceFlagsState(stateID: CGEventSourceStateID) -> CGEventFlags;
+}
+
+pub type TISInputSourceRef = *mut ::std::os::raw::c_void;
+pub type CFDataRef = *const ::std::os::raw::c_void;
+pub type UniChar = u16;
+pub type UniCharCount = ::std::os::raw::c_ulong;
+pub const kUCKeyActionDisplay: u16 = 3;
+pub const kUCKeyTranslateNoDeadKeysMask: OptionBits = 1;
+
+#[link(name = "Carbon", kind = "framework")]
+extern "C" {
+    pub static kTISPropertyUnicodeKeyLayoutData: CFStringRef;
+    pub fn TISCopyCurrentKeyboardLayoutInputSource() -> TISInputSourceRef;
+    pub fn TISGetInputSourceProperty(
+        inputSource: TISInputSourceRef,
+        propertyKey: CFStringRef,
+    ) -> *mut ::std::os::raw::c_void;
+    pub fn LMGetKbdType() -> u8;
+    pub fn UCKeyTranslate(
+        keyLayoutPtr: *const ::std::os::raw::c_void,
+        virtualKeyCode: u16,
+        keyAction: u16,
+        modifierKeyState: UInt32,
+        keyboardType: UInt32,
+        keyTranslateOptions: OptionBits,
+        deadKeyState: *mut UInt32,
+        maxStringLength: UniCharCount,
+        actualStringLength: *mut UniCharCount,
+        unicodeString: *mut UniChar,
+    ) -> OSStatus;
+}
+
+#[link(name = "CoreFoundation", kind = "framework")]
+extern "C" {
+    pub fn CFDataGetBytePtr(theData: CFDataRef) -> *const u8;
+}
@@ -1,28 +1,66 @@
-use std::{collections::BTreeMap, ffi::c_void, sync::Mutex};
+use std::{
+    collections::BTreeMap,
+    ffi::c_void,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
 
 use keyboard_types::{Code, Modifiers};
 
-use crate::{hotkey::HotKey, GlobalHotKeyEvent};
+use crate::{
+    activation::Activations,
+    hotkey::{HotKey, HotKeySequence, ModifierSides, SequenceTracker, Trigger},
+    platform_impl::ParkedHotKeys,
+    FailureReason, GlobalHotKeyEvent,
+};
 
 use self::ffi::{
-    kEventClassKeyboard, kEventHotKeyPressed, kEventHotKeyReleased, kEventParamDirectObject, noErr,
-    typeEventHotKeyID, EventHandlerCallRef, EventHandlerRef, EventHotKeyID, EventHotKeyRef,
-    EventRef, EventTypeSpec, GetApplicationEventTarget, GetEventKind, GetEventParameter,
-    InstallEventHandler, OSStatus, RegisterEventHotKey, RemoveEventHandler, UnregisterEventHotKey,
+    eventHotKeyExistsErr, kCFRunLoopCommonModes, kCGEventSourceStateHIDSystemState,
+    kEventClassKeyboard, kEventHotKeyPressed, kEventHotKeyReleased, kEventParamDirectObject,
+    kTISPropertyUnicodeKeyLayoutData, kUCKeyActionDisplay, kUCKeyTranslateNoDeadKeysMask, noErr,
+    typeEventHotKeyID, CFAbsoluteTimeGetCurrent, CFDataGetBytePtr, CFRelease, CFRunLoopAddTimer,
+    CFRunLoopGetMain, CFRunLoopTimerContext, CFRunLoopTimerCreate, CFRunLoopTimerInvalidate,
+    CFRunLoopTimerRef, CGEventSourceFlagsState, EventHandlerCallRef, EventHandlerRef,
+    EventHotKeyID, EventHotKeyRef, EventRef, EventTypeSpec, GetApplicationEventTarget,
+    GetEventKind, GetEventParameter, InstallEventHandler, LMGetKbdType, OSStatus,
+    RegisterEventHotKey, RemoveEventHandler, TISCopyCurrentKeyboardLayoutInputSource,
+    TISGetInputSourceProperty, UCKeyTranslate, UnregisterEventHotKey, NX_DEVICELALTKEYMASK,
+    NX_DEVICELCMDKEYMASK, NX_DEVICELCTLKEYMASK, NX_DEVICELSHFTKEYMASK, NX_DEVICERALTKEYMASK,
+    NX_DEVICERCMDKEYMASK, NX_DEVICERCTLKEYMASK, NX_DEVICERSHFTKEYMASK,
 };
 
 mod ffi;
 
 pub struct GlobalHotKeyManager {
     event_handler_ptr: EventHandlerRef,
-    hotkeys: Mutex<BTreeMap<u32, HotKeyWrapper>>,
+    state: Box<Mutex<State>>,
 }
 
 unsafe impl Send for GlobalHotKeyManager {}
 unsafe impl Sync for GlobalHotKeyManager {}
 
+/// State shared with [`hotkey_handler`] through its user data.
+#[derive(Default)]
+struct State {
+    /// The grabbed hotkeys, keyed by their id.
+    hotkeys: BTreeMap<u32, HotKey>,
+    /// The registered hotkeys which aren't grabbed, because they are disabled or the manager is paused.
+    parked: ParkedHotKeys,
+    /// The `RegisterEventHotKey` registrations, shared between hotkeys and sequence strokes,
+    /// keyed by the registration's id, see [`grab_id`].
+    grabs: BTreeMap<u32, HotKeyWrapper>,
+    sequences: SequenceTracker,
+    sequence_grabs: Vec<HotKey>,
+    sequence_timer: Option<CFRunLoopTimerRef>,
+    /// Ids of the hotkeys and sequence strokes that fired for a registration
+    /// which hasn't been released yet.
+    pressed: BTreeMap<u32, Vec<u32>>,
+    /// The options of the hotkeys, which turn the reported presses into the emitted events.
+    activations: Arc<Activations>,
+}
+
 impl GlobalHotKeyManager {
-    pub fn new() -> crate::Result<Self> {
+    pub fn new(activations: Arc<Activations>) -> crate::Result<Self> {
         let pressed_event_type = EventTypeSpec {
             eventClass: kEventClassKeyboard,
             eventKind: kEventHotKeyPressed,
@@ -33,6 +71,11 @@ impl GlobalHotKeyManager {
         };
         let event_types = [pressed_event_type, released_event_type];
 
+        let state = Box::new(Mutex::new(State {
+            activations,
+            ..Default::default()
+        }));
+
         let ptr = unsafe {
             let mut handler_ref: EventHandlerRef = std::mem::zeroed();
 
@@ -41,7 +84,7 @@ impl GlobalHotKeyManager {
                 Some(hotkey_handler),
                 2,
                 event_types.as_ptr(),
-                std::ptr::null_mut(),
+                &*state as *const _ as *mut _,
                 &mut handler_ref,
             );
 
@@ -54,90 +97,171 @@ impl GlobalHotKeyManager {
 
         Ok(Self {
             event_handler_ptr: ptr,
-            hotkeys: Mutex::new(BTreeMap::new()),
+            state,
         })
     }
 
     pub fn register(&self, hotkey: HotKey) -> crate::Result<()> {
-        let mut mods: u32 = 0;
-        if hotkey.mods.contains(Modifiers::SHIFT) {
-            mods |= 512;
-        }
-        if hotkey.mods.intersects(Modifiers::SUPER | Modifiers::META) {
-            mods |= 256;
-        }
-        if hotkey.mods.contains(Modifiers::ALT) {
-            mods |= 2048;
-        }
-        if hotkey.mods.contains(Modifiers::CONTROL) {
-            mods |= 4096;
-        }
-
-        if let Some(scan_code) = key_to_scancode(hotkey.key) {
-            let hotkey_id = EventHotKeyID {
-                id: hotkey.id(),
-                signature: {
-                    let mut res: u32 = 0;
-                    // can't find a resource for "htrs" so we construct it manually
-                    // the construction method below is taken from https://github.com/soffes/HotKey/blob/c13662730cb5bc28de4a799854bbb018a90649bf/Sources/HotKey/HotKeysController.swift#L27
-                    // and confirmed by applying the same method to `kEventParamDragRef` which is equal to `drag` in C
-                    // and converted to `1685217639` by rust-bindgen.
-                    for c in "htrs".chars() {
-                        res = (res << 8) + c as u32;
-                    }
-                    res
-                },
-            };
+        let mut state = self.state.lock().unwrap();
+        if state.hotkeys.contains_key(&hotkey.id()) || state.parked.contains(hotkey.id()) {
+            return Err(crate::Error::AlreadyRegistered(hotkey));
+        }
 
-            let ptr = unsafe {
-                let mut hotkey_ref: EventHotKeyRef = std::mem::zeroed();
-                let result = RegisterEventHotKey(
-                    scan_code,
-                    mods,
-                    hotkey_id,
-                    GetApplicationEventTarget(),
-                    0,
-                    &mut hotkey_ref,
-                );
+        grab(&mut state.grabs, hotkey)?;
+        // hotkeys registered while paused are grabbed on resume,
+        // grabbing them once still reports a combination that can't be grabbed
+        if state.parked.is_paused() {
+            ungrab(&mut state.grabs, hotkey)?;
+            state.parked.insert(hotkey, true);
+            return Ok(());
+        }
+        state.hotkeys.insert(hotkey.id(), hotkey);
+        Ok(())
+    }
 
-                if result != noErr as _ {
-                    return Err(crate::Error::FailedToRegister(format!(
-                        "Unable to register hotkey: {}",
-                        hotkey.key
-                    )));
-                }
+    pub fn unregister(&self, hotkey: HotKey) -> crate::Result<()> {
+        let mut state = self.state.lock().unwrap();
+        if state.parked.remove(hotkey.id()).is_some() {
+            return Ok(());
+        }
+        if state.hotkeys.remove(&hotkey.id()).is_none() {
+            return Err(crate::Error::FailedToUnRegister {
+                hotkey,
+                reason: FailureReason::NotRegistered,
+            });
+        }
 
-                hotkey_ref
-            };
+        ungrab(&mut state.grabs, hotkey)
+    }
 
-            self.hotkeys
-                .lock()
-                .unwrap()
-                .insert(hotkey.id(), HotKeyWrapper { ptr, hotkey });
-            Ok(())
-        } else {
-            Err(crate::Error::FailedToRegister(format!(
-                "Unable to register accelerator (unknown scancode for this key: {}).",
-                hotkey.key
-            )))
+    pub fn rebind(&self, old: HotKey, new: HotKey) -> crate::Result<()> {
+        let mut state = self.state.lock().unwrap();
+        if !state.hotkeys.contains_key(&old.id()) && !state.parked.contains(old.id()) {
+            return Err(crate::Error::FailedToUnRegister {
+                hotkey: old,
+                reason: FailureReason::NotRegistered,
+            });
+        }
+        if state.hotkeys.contains_key(&new.id()) || state.parked.contains(new.id()) {
+            return Err(crate::Error::AlreadyRegistered(new));
+        }
+        // a parked hotkey isn't grabbed, the new one takes its place until it's enabled or resumed,
+        // after a grab probe so an unsupported or taken hotkey fails now instead of on resume
+        if state.parked.contains(old.id()) {
+            grab(&mut state.grabs, new)?;
+            ungrab(&mut state.grabs, new)?;
+            if let Some((_, enabled)) = state.parked.remove(old.id()) {
+                state.parked.insert(new, enabled);
+            }
+            return Ok(());
         }
+
+        // grab the new hotkey first, so the old one stays registered if that fails
+        grab(&mut state.grabs, new)?;
+        if let Err(e) = ungrab(&mut state.grabs, old) {
+            let _ = ungrab(&mut state.grabs, new);
+            let _ = grab(&mut state.grabs, old);
+            return Err(e);
+        }
+        state.hotkeys.remove(&old.id());
+        state.hotkeys.insert(new.id(), new);
+        Ok(())
     }
 
-    pub fn unregister(&self, hotkey: HotKey) -> crate::Result<()> {
-        if let Some(hotkeywrapper) = self.hotkeys.lock().unwrap().remove(&hotkey.id()) {
-            unsafe { self.unregister_hotkey_ptr(hotkeywrapper.ptr, hotkey) }?;
+    pub fn set_enabled(&self, hotkey: HotKey, enabled: bool) -> crate::Result<()> {
+        let mut state = self.state.lock().unwrap();
+        let id = hotkey.id();
+        if state.hotkeys.contains_key(&id) {
+            if !enabled {
+                state.hotkeys.remove(&id);
+                state.parked.insert(hotkey, false);
+                return ungrab(&mut state.grabs, hotkey);
+            }
+            return Ok(());
+        }
+        if !state.parked.contains(id) {
+            let reason = FailureReason::NotRegistered;
+            return Err(if enabled {
+                crate::Error::FailedToRegister { hotkey, reason }
+            } else {
+                crate::Error::FailedToUnRegister { hotkey, reason }
+            });
         }
 
+        if let Some(hotkey) = state.parked.set_enabled(id, enabled) {
+            if let Err(e) = grab(&mut state.grabs, hotkey) {
+                state.parked.insert(hotkey, false);
+                return Err(e);
+            }
+            state.hotkeys.insert(id, hotkey);
+        }
         Ok(())
     }
 
+    pub fn pause_all(&self) -> crate::Result<()> {
+        let mut state = self.state.lock().unwrap();
+        if state.parked.is_paused() {
+            return Ok(());
+        }
+
+        state.parked.pause();
+        let mut result = Ok(());
+        for hotkey in std::mem::take(&mut state.hotkeys).into_values() {
+            state.parked.insert(hotkey, true);
+            let ungrabbed = ungrab(&mut state.grabs, hotkey);
+            if result.is_ok() {
+                result = ungrabbed;
+            }
+        }
+        sync_sequence_grabs(&mut state);
+        result
+    }
+
+    pub fn resume_all(&self) -> crate::Result<()> {
+        let mut state = self.state.lock().unwrap();
+        if !state.parked.is_paused() {
+            return Ok(());
+        }
+
+        // hotkeys that can't be grabbed again stay parked as disabled
+        let mut result = Ok(());
+        for hotkey in state.parked.resume() {
+            match grab(&mut state.grabs, hotkey) {
+                Ok(()) => {
+                    state.hotkeys.insert(hotkey.id(), hotkey);
+                }
+                Err(e) => {
+                    state.parked.insert(hotkey, false);
+                    if result.is_ok() {
+                        result = Err(e);
+                    }
+                }
+            }
+        }
+        sync_sequence_grabs(&mut state);
+        result
+    }
+
     pub fn register_all(&self, hotkeys: &[HotKey]) -> crate::Result<()> {
-        for hotkey in hotkeys {
-            self.register(*hotkey)?;
+        for (i, hotkey) in hotkeys.iter().enumerate() {
+            if let Err(e) = self.register(*hotkey) {
+                // all or nothing, release what this batch already registered
+                for hotkey in hotkeys[..i].iter().rev() {
+                    let _ = self.unregister(*hotkey);
+                }
+                return Err(e);
+            }
         }
         Ok(())
     }
 
+    pub fn register_each(&self, hotkeys: &[HotKey]) -> Vec<(HotKey, crate::Result<()>)> {
+        hotkeys
+            .iter()
+            .map(|&hotkey| (hotkey, self.register(hotkey)))
+            .collect()
+    }
+
     pub fn unregister_all(&self, hotkeys: &[HotKey]) -> crate::Result<()> {
         for hotkey in hotkeys {
             self.unregister(*hotkey)?;
@@ -145,24 +269,74 @@ impl GlobalHotKeyManager {
         Ok(())
     }
 
-    unsafe fn unregister_hotkey_ptr(
-        &self,
-        ptr: EventHotKeyRef,
-        hotkey: HotKey,
-    ) -> crate::Result<()> {
-        if UnregisterEventHotKey(ptr) != noErr as _ {
-            return Err(crate::Error::FailedToUnRegister(hotkey));
+    pub fn register_sequence(&self, sequence: HotKeySequence) -> crate::Result<()> {
+        if let Some(stroke) = sequence
+            .strokes()
+            .iter()
+            .find(|s| trigger_to_scancode(s.trigger()).is_none())
+        {
+            return Err(crate::Error::FailedToRegister {
+                hotkey: *stroke,
+                reason: FailureReason::UnsupportedKey,
+            });
+        }
+
+        let mut state = self.state.lock().unwrap();
+        let first = sequence.strokes()[0];
+        let id = sequence.id();
+        if !state.sequences.register(sequence.clone()) {
+            return Err(crate::Error::SequenceAlreadyRegistered(sequence));
+        }
+        sync_sequence_grabs(&mut state);
+
+        // nothing is grabbed while paused, the sequence is grabbed on resume
+        if !state.parked.is_paused() && !state.sequence_grabs.contains(&first) {
+            state.sequences.unregister(id);
+            sync_sequence_grabs(&mut state);
+            return Err(crate::Error::FailedToRegister {
+                hotkey: first,
+                reason: FailureReason::GrabbedByOtherClient,
+            });
         }
 
         Ok(())
     }
+
+    pub fn unregister_sequence(&self, sequence: &HotKeySequence) -> crate::Result<()> {
+        let mut state = self.state.lock().unwrap();
+        if state.sequences.unregister(sequence.id()).is_none() {
+            return Err(crate::Error::FailedToUnRegister {
+                hotkey: sequence.strokes()[0],
+                reason: FailureReason::NotRegistered,
+            });
+        }
+        sync_sequence_grabs(&mut state);
+        unsafe { schedule_sequence_timer(&mut state, &*self.state) };
+        Ok(())
+    }
+
+    pub fn hotkeys(&self) -> Vec<HotKey> {
+        let state = self.state.lock().unwrap();
+        state
+            .hotkeys
+            .values()
+            .copied()
+            .chain(state.parked.hotkeys())
+            .collect()
+    }
 }
 
 impl Drop for GlobalHotKeyManager {
     fn drop(&mut self) {
-        let hotkeys = self.hotkeys.lock().unwrap().clone();
-        for (_, hotkeywrapper) in hotkeys {
-            let _ = self.unregister(hotkeywrapper.hotkey);
+        let mut state = self.state.lock().unwrap();
+        if let Some(timer) = state.sequence_timer.take() {
+            unsafe {
+                CFRunLoopTimerInvalidate(timer);
+                CFRelease(timer as _);
+            }
+        }
+        for (_, hotkeywrapper) in std::mem::take(&mut state.grabs) {
+            unsafe { UnregisterEventHotKey(hotkeywrapper.ptr) };
         }
         unsafe {
             RemoveEventHandler(self.event_handler_ptr);
@@ -170,10 +344,229 @@ impl Drop for GlobalHotKeyManager {
     }
 }
 
+/// Registers the hotkey with the OS, unless it is already registered for another user.
+///
+/// `RegisterEventHotKey` doesn't know about modifier sides, so hotkeys that only differ
+/// in their sides share a registration.
+fn grab(grabs: &mut BTreeMap<u32, HotKeyWrapper>, hotkey: HotKey) -> crate::Result<()> {
+    // modifier-only, mouse button and wheel hotkeys can't be registered with `RegisterEventHotKey`
+    if let Trigger::ModifiersOnly | Trigger::Mouse(_) | Trigger::Wheel(_) = hotkey.trigger() {
+        return Err(crate::Error::FailedToRegister {
+            hotkey,
+            reason: FailureReason::UnsupportedKey,
+        });
+    }
+    if hotkey
+        .mods
+        .intersects(Modifiers::HYPER | Modifiers::CAPS_LOCK)
+    {
+        return Err(crate::Error::FailedToRegister {
+            hotkey,
+            reason: FailureReason::UnsupportedModifiers,
+        });
+    }
+
+    let mut mods: u32 = 0;
+    if hotkey.mods.contains(Modifiers::SHIFT) {
+        mods |= 512;
+    }
+    if hotkey.mods.intersects(Modifiers::SUPER | Modifiers::META) {
+        mods |= 256;
+    }
+    if hotkey.mods.contains(Modifiers::ALT) {
+        mods |= 2048;
+    }
+    if hotkey.mods.contains(Modifiers::CONTROL) {
+        mods |= 4096;
+    }
+    // Option is the AltGr of macOS keyboards
+    if hotkey.mods.contains(Modifiers::ALT_GRAPH) {
+        mods |= 2048;
+    }
+
+    let Some(scan_code) = trigger_to_scancode(hotkey.trigger()) else {
+        return Err(crate::Error::FailedToRegister {
+            hotkey,
+            reason: FailureReason::UnsupportedKey,
+        });
+    };
+
+    // hotkeys that only differ in their sides or in modifiers macOS doesn't tell apart,
+    // like Super and Meta, share the registration
+    let id = grab_id(mods, scan_code);
+    if let Some(hotkeywrapper) = grabs.get_mut(&id) {
+        hotkeywrapper.users.push(hotkey.id());
+        return Ok(());
+    }
+
+    let hotkey_id = EventHotKeyID {
+        id,
+        signature: {
+            let mut res: u32 = 0;
+            // can't find a resource for "htrs" so we construct it manually
+            // the construction method below is taken from https://github.com/soffes/HotKey/blob/c13662730cb5bc28de4a799854bbb018a90649bf/Sources/HotKey/HotKeysController.swift#L27
+            // and confirmed by applying the same method to `kEventParamDragRef` which is equal to `drag` in C
+            // and converted to `1685217639` by rust-bindgen.
+            for c in "htrs".chars() {
+                res = (res << 8) + c as u32;
+            }
+            res
+        },
+    };
+
+    let ptr = unsafe {
+        let mut hotkey_ref: EventHotKeyRef = std::mem::zeroed();
+        let result = RegisterEventHotKey(
+            scan_code,
+            mods,
+            hotkey_id,
+            GetApplicationEventTarget(),
+            0,
+            &mut hotkey_ref,
+        );
+
+        if result != noErr as _ {
+            let reason = if result == eventHotKeyExistsErr {
+                FailureReason::GrabbedByOtherClient
+            } else {
+                FailureReason::Os(result)
+            };
+            return Err(crate::Error::FailedToRegister { hotkey, reason });
+        }
+
+        hotkey_ref
+    };
+
+    grabs.insert(
+        id,
+        HotKeyWrapper {
+            ptr,
+            users: vec![hotkey.id()],
+        },
+    );
+    Ok(())
+}
+
+fn ungrab(grabs: &mut BTreeMap<u32, HotKeyWrapper>, hotkey: HotKey) -> crate::Result<()> {
+    let Some((&id, hotkeywrapper)) = grabs
+        .iter_mut()
+        .find(|(_, wrapper)| wrapper.users.contains(&hotkey.id()))
+    else {
+        return Ok(());
+    };
+
+    if let Some(i) = hotkeywrapper.users.iter().position(|&u| u == hotkey.id()) {
+        hotkeywrapper.users.remove(i);
+    }
+    if hotkeywrapper.users.is_empty() {
+        let hotkeywrapper = grabs.remove(&id).unwrap();
+        let status = unsafe { UnregisterEventHotKey(hotkeywrapper.ptr) };
+        if status != noErr as _ {
+            return Err(crate::Error::FailedToUnRegister {
+                hotkey,
+                reason: FailureReason::Os(status),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Returns the id of the `RegisterEventHotKey` registration of these modifiers and key,
+/// the modifier masks are above the 7 bits of the scancodes.
+fn grab_id(mods: u32, scan_code: u32) -> u32 {
+    mods | scan_code
+}
+
+/// Grabs the strokes the sequences are currently waiting for and releases the others.
+fn sync_sequence_grabs(state: &mut State) {
+    // nothing is grabbed while paused
+    let wanted = if state.parked.is_paused() {
+        Vec::new()
+    } else {
+        state.sequences.grabbed_strokes()
+    };
+    let State {
+        grabs,
+        sequence_grabs,
+        ..
+    } = state;
+
+    sequence_grabs.retain(|stroke| {
+        let keep = wanted.contains(stroke);
+        if !keep {
+            let _ = ungrab(grabs, *stroke);
+        }
+        keep
+    });
+
+    for stroke in wanted {
+        if !sequence_grabs.contains(&stroke) && grab(grabs, stroke).is_ok() {
+            sequence_grabs.push(stroke);
+        }
+    }
+}
+
+/// Returns the sides of the modifier keys that are currently held down.
+fn pressed_sides() -> ModifierSides {
+    let flags = unsafe { CGEventSourceFlagsState(kCGEventSourceStateHIDSystemState) };
+    [
+        (ModifierSides::SHIFT_LEFT, NX_DEVICELSHFTKEYMASK),
+        (ModifierSides::SHIFT_RIGHT, NX_DEVICERSHFTKEYMASK),
+        (ModifierSides::CONTROL_LEFT, NX_DEVICELCTLKEYMASK),
+        (ModifierSides::CONTROL_RIGHT, NX_DEVICERCTLKEYMASK),
+        (ModifierSides::ALT_LEFT, NX_DEVICELALTKEYMASK),
+        (ModifierSides::ALT_RIGHT, NX_DEVICERALTKEYMASK),
+        (ModifierSides::SUPER_LEFT, NX_DEVICELCMDKEYMASK),
+        (ModifierSides::SUPER_RIGHT, NX_DEVICERCMDKEYMASK),
+    ]
+    .into_iter()
+    .filter(|(_, mask)| flags & mask != 0)
+    .fold(ModifierSides::empty(), |sides, (side, _)| sides | side)
+}
+
+/// (Re)schedules the timer aborting the sequences in progress once they time out.
+unsafe fn schedule_sequence_timer(state: &mut State, info: *const Mutex<State>) {
+    if let Some(timer) = state.sequence_timer.take() {
+        CFRunLoopTimerInvalidate(timer);
+        CFRelease(timer as _);
+    }
+
+    if let Some(deadline) = state.sequences.deadline() {
+        let timeout = deadline.saturating_duration_since(Instant::now());
+        let mut context = CFRunLoopTimerContext {
+            version: 0,
+            info: info as *mut _,
+            retain: std::ptr::null(),
+            release: std::ptr::null(),
+            copyDescription: std::ptr::null(),
+        };
+        let timer = CFRunLoopTimerCreate(
+            std::ptr::null(),
+            CFAbsoluteTimeGetCurrent() + timeout.as_secs_f64(),
+            0.0,
+            0,
+            0,
+            Some(sequence_timer_handler),
+            &mut context,
+        );
+        CFRunLoopAddTimer(CFRunLoopGetMain(), timer, kCFRunLoopCommonModes);
+        state.sequence_timer = Some(timer);
+    }
+}
+
+unsafe extern "C" fn sequence_timer_handler(_timer: CFRunLoopTimerRef, info: *mut c_void) {
+    let info = info as *const Mutex<State>;
+    let mut state = (*info).lock().unwrap();
+    if state.sequences.expire(Instant::now()) {
+        sync_sequence_grabs(&mut state);
+    }
+    schedule_sequence_timer(&mut state, info);
+}
+
 unsafe extern "C" fn hotkey_handler(
     _next_handler: EventHandlerCallRef,
     event: EventRef,
-    _user_data: *mut c_void,
+    user_data: *mut c_void,
 ) -> OSStatus {
     let mut event_hotkey: EventHotKeyID = std::mem::zeroed();
 
@@ -188,29 +581,140 @@ unsafe extern "C" fn hotkey_handler(
     );
 
     if result == noErr as _ {
-        let event_kind = GetEventKind(event);
-        match event_kind {
-            #[allow(non_upper_case_globals)]
-            kEventHotKeyPressed => GlobalHotKeyEvent::send(GlobalHotKeyEvent {
-                id: event_hotkey.id,
-                state: crate::HotKeyState::Pressed,
-            }),
-            #[allow(non_upper_case_globals)]
-            kEventHotKeyReleased => GlobalHotKeyEvent::send(GlobalHotKeyEvent {
-                id: event_hotkey.id,
-                state: crate::HotKeyState::Released,
-            }),
-            _ => {}
-        };
+        let info = user_data as *const Mutex<State>;
+        let mut events = Vec::new();
+        {
+            let mut state = (*info).lock().unwrap();
+            let id = event_hotkey.id;
+
+            let event_kind = GetEventKind(event);
+            match event_kind {
+                #[allow(non_upper_case_globals)]
+                kEventHotKeyPressed => {
+                    let sides = pressed_sides();
+                    let users = state
+                        .grabs
+                        .get(&id)
+                        .map(|grab| grab.users.clone())
+                        .unwrap_or_default();
+                    let matches = |hotkey: &HotKey| {
+                        users.contains(&hotkey.id()) && hotkey.sides().satisfied_by(sides)
+                    };
+
+                    let mut pressed: Vec<u32> = state
+                        .hotkeys
+                        .values()
+                        .filter(|h| matches(h))
+                        .map(|h| h.id())
+                        .collect();
+                    for &id in &pressed {
+                        events.push(GlobalHotKeyEvent::new(id, crate::HotKeyState::Pressed));
+                    }
+
+                    let stroke = state
+                        .sequence_grabs
+                        .iter()
+                        .filter(|s| matches(s))
+                        // prefer `RCtrl+K` over `Ctrl+K` when both are waited for
+                        .max_by_key(|s| s.sides().bits().count_ones())
+                        .map(|s| s.id());
+                    if let Some(stroke) = stroke {
+                        if !pressed.contains(&stroke) {
+                            pressed.push(stroke);
+                        }
+                        if let Some(id) = state.sequences.press(stroke, Instant::now()) {
+                            events.push(GlobalHotKeyEvent::new(id, crate::HotKeyState::Pressed));
+                        }
+                        sync_sequence_grabs(&mut state);
+                        schedule_sequence_timer(&mut state, info);
+                    }
+                    state.pressed.insert(id, pressed);
+                }
+                #[allow(non_upper_case_globals)]
+                kEventHotKeyReleased => {
+                    let pressed = state.pressed.remove(&id).unwrap_or_default();
+                    for id in pressed {
+                        if state.hotkeys.contains_key(&id) {
+                            events.push(GlobalHotKeyEvent::new(id, crate::HotKeyState::Released));
+                        }
+                        if let Some(id) = state.sequences.release(id) {
+                            events.push(GlobalHotKeyEvent::new(id, crate::HotKeyState::Released));
+                            sync_sequence_grabs(&mut state);
+                        }
+                    }
+                }
+                _ => {}
+            };
+        }
+
+        // sent after releasing the lock so event handlers can use the manager
+        if !events.is_empty() {
+            let activations = (*info).lock().unwrap().activations.clone();
+            for event in events {
+                activations.send(event);
+            }
+        }
     }
 
     noErr as _
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 struct HotKeyWrapper {
     ptr: EventHotKeyRef,
-    hotkey: HotKey,
+    /// The ids of the hotkeys and sequence strokes using the registration.
+    users: Vec<u32>,
+}
+
+fn trigger_to_scancode(trigger: Trigger) -> Option<u32> {
+    match trigger {
+        Trigger::Key(key) => key_to_scancode(key),
+        Trigger::Character(c) => {
+            char_to_scancode(c).or_else(|| Trigger::fallback_key(c).and_then(key_to_scancode))
+        }
+        _ => None,
+    }
+}
+
+/// Finds the key typing `c` without modifiers in the current keyboard layout.
+fn char_to_scancode(c: char) -> Option<u32> {
+    unsafe {
+        let source = TISCopyCurrentKeyboardLayoutInputSource();
+        if source.is_null() {
+            return None;
+        }
+        let data = TISGetInputSourceProperty(source, kTISPropertyUnicodeKeyLayoutData);
+        let scancode = if data.is_null() {
+            None
+        } else {
+            let layout = CFDataGetBytePtr(data as _);
+            let keyboard_type = LMGetKbdType() as u32;
+            (0..128u16).find(|&keycode| {
+                let mut dead_key_state = 0;
+                let mut len = 0;
+                let mut chars = [0u16; 4];
+                let result = UCKeyTranslate(
+                    layout as _,
+                    keycode,
+                    kUCKeyActionDisplay,
+                    0,
+                    keyboard_type,
+                    kUCKeyTranslateNoDeadKeysMask,
+                    &mut dead_key_state,
+                    chars.len() as _,
+                    &mut len,
+                    chars.as_mut_ptr(),
+                );
+                result == noErr as _
+                    && char::decode_utf16(chars[..len as usize].iter().copied())
+                        .flat_map(|c| c.ok())
+                        .flat_map(char::to_lowercase)
+                        .eq(std::iter::once(c))
+            })
+        };
+        CFRelease(source as _);
+        scancode.map(u32::from)
+    }
 }
 
 pub fn key_to_scancode(code: Code) -> Option<u32> {
@@ -320,6 +824,14 @@ pub fn key_to_scancode(code: Code) -> Option<u32> {
         Code::ArrowUp => Some(0x7e),
         Code::CapsLock => Some(0x39),
         Code::PrintScreen => Some(0x46),
+        Code::IntlBackslash => Some(0x0a),
+        Code::IntlYen => Some(0x5d),
+        Code::IntlRo => Some(0x5e),
+        Code::NumpadComma => Some(0x5f),
+        Code::Lang2 => Some(0x66),
+        Code::Lang1 => Some(0x68),
+        Code::ContextMenu => Some(0x6e),
+        Code::Help => Some(0x72),
         _ => None,
     }
 }
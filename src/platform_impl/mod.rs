@@ -31,3 +31,6 @@ mod platform;
 mod platform;
 
 pub(crate) use self::platform::*;
+
+mod parked;
+pub(crate) use self::parked::ParkedHotKeys;
@@ -5,34 +5,137 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use crate::hotkey::HotKey;
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+};
 
-pub struct GlobalHotKeyManager {}
+use crate::{
+    activation::Activations,
+    hotkey::{HotKey, HotKeySequence},
+};
+
+pub struct GlobalHotKeyManager {
+    hotkeys: Mutex<BTreeMap<u32, HotKey>>,
+    sequences: Mutex<BTreeMap<u32, HotKeySequence>>,
+}
 
 impl GlobalHotKeyManager {
-    pub fn new() -> crate::Result<Self> {
-        Ok(Self {})
+    pub fn new(_activations: Arc<Activations>) -> crate::Result<Self> {
+        Ok(Self {
+            hotkeys: Mutex::new(BTreeMap::new()),
+            sequences: Mutex::new(BTreeMap::new()),
+        })
     }
 
     pub fn register(&self, hotkey: HotKey) -> crate::Result<()> {
+        let mut hotkeys = self.hotkeys.lock().unwrap();
+        if hotkeys.contains_key(&hotkey.id()) {
+            return Err(crate::Error::AlreadyRegistered(hotkey));
+        }
+        hotkeys.insert(hotkey.id(), hotkey);
         Ok(())
     }
 
     pub fn unregister(&self, hotkey: HotKey) -> crate::Result<()> {
+        if self.hotkeys.lock().unwrap().remove(&hotkey.id()).is_none() {
+            return Err(crate::Error::FailedToUnRegister {
+                hotkey,
+                reason: crate::FailureReason::NotRegistered,
+            });
+        }
+        Ok(())
+    }
+
+    pub fn rebind(&self, old: HotKey, new: HotKey) -> crate::Result<()> {
+        let mut hotkeys = self.hotkeys.lock().unwrap();
+        if !hotkeys.contains_key(&old.id()) {
+            return Err(crate::Error::FailedToUnRegister {
+                hotkey: old,
+                reason: crate::FailureReason::NotRegistered,
+            });
+        }
+        if hotkeys.contains_key(&new.id()) {
+            return Err(crate::Error::AlreadyRegistered(new));
+        }
+        hotkeys.remove(&old.id());
+        hotkeys.insert(new.id(), new);
+        Ok(())
+    }
+
+    pub fn set_enabled(&self, hotkey: HotKey, enabled: bool) -> crate::Result<()> {
+        if !self.hotkeys.lock().unwrap().contains_key(&hotkey.id()) {
+            let reason = crate::FailureReason::NotRegistered;
+            return Err(if enabled {
+                crate::Error::FailedToRegister { hotkey, reason }
+            } else {
+                crate::Error::FailedToUnRegister { hotkey, reason }
+            });
+        }
+        Ok(())
+    }
+
+    pub fn pause_all(&self) -> crate::Result<()> {
+        Ok(())
+    }
+
+    pub fn resume_all(&self) -> crate::Result<()> {
         Ok(())
     }
 
     pub fn register_all(&self, hotkeys: &[HotKey]) -> crate::Result<()> {
-        for hotkey in hotkeys {
-            self.register(*hotkey)?;
+        for (i, hotkey) in hotkeys.iter().enumerate() {
+            if let Err(e) = self.register(*hotkey) {
+                // all or nothing, release what this batch already registered
+                for hotkey in hotkeys[..i].iter().rev() {
+                    let _ = self.unregister(*hotkey);
+                }
+                return Err(e);
+            }
         }
         Ok(())
     }
 
+    pub fn register_each(&self, hotkeys: &[HotKey]) -> Vec<(HotKey, crate::Result<()>)> {
+        hotkeys
+            .iter()
+            .map(|&hotkey| (hotkey, self.register(hotkey)))
+            .collect()
+    }
+
     pub fn unregister_all(&self, hotkeys: &[HotKey]) -> crate::Result<()> {
         for hotkey in hotkeys {
             self.unregister(*hotkey)?;
         }
         Ok(())
     }
+
+    pub fn register_sequence(&self, sequence: HotKeySequence) -> crate::Result<()> {
+        let mut sequences = self.sequences.lock().unwrap();
+        if sequences.contains_key(&sequence.id()) {
+            return Err(crate::Error::SequenceAlreadyRegistered(sequence));
+        }
+        sequences.insert(sequence.id(), sequence);
+        Ok(())
+    }
+
+    pub fn unregister_sequence(&self, sequence: &HotKeySequence) -> crate::Result<()> {
+        if self
+            .sequences
+            .lock()
+            .unwrap()
+            .remove(&sequence.id())
+            .is_none()
+        {
+            return Err(crate::Error::FailedToUnRegister {
+                hotkey: sequence.strokes()[0],
+                reason: crate::FailureReason::NotRegistered,
+            });
+        }
+        Ok(())
+    }
+
+    pub fn hotkeys(&self) -> Vec<HotKey> {
+        self.hotkeys.lock().unwrap().values().copied().collect()
+    }
 }
@@ -0,0 +1,104 @@
+// Copyright 2022-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::collections::BTreeMap;
+
+use crate::hotkey::HotKey;
+
+/// Registered hotkeys whose grab is released, because they are disabled or the manager is paused.
+///
+/// Platform implementations keep the grabbed hotkeys in their own state and move them here
+/// with their enabled flag when they release them, so parked hotkeys never emit events.
+#[derive(Debug, Default)]
+pub(crate) struct ParkedHotKeys {
+    paused: bool,
+    //                  id     hotkey  enabled
+    hotkeys: BTreeMap<u32, (HotKey, bool)>,
+}
+
+impl ParkedHotKeys {
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn contains(&self, id: u32) -> bool {
+        self.hotkeys.contains_key(&id)
+    }
+
+    pub fn hotkeys(&self) -> impl Iterator<Item = HotKey> + '_ {
+        self.hotkeys.values().map(|&(hotkey, _)| hotkey)
+    }
+
+    /// Parks a registered hotkey whose grab is released.
+    pub fn insert(&mut self, hotkey: HotKey, enabled: bool) {
+        self.hotkeys.insert(hotkey.id(), (hotkey, enabled));
+    }
+
+    /// Removes a parked hotkey, returns it with its enabled flag.
+    pub fn remove(&mut self, id: u32) -> Option<(HotKey, bool)> {
+        self.hotkeys.remove(&id)
+    }
+
+    /// Enables or disables a parked hotkey.
+    ///
+    /// Returns the hotkey when it should be grabbed again, it is then no longer parked.
+    pub fn set_enabled(&mut self, id: u32, enabled: bool) -> Option<HotKey> {
+        let (hotkey, _) = self.hotkeys.get(&id)?;
+        if enabled && !self.paused {
+            return self.hotkeys.remove(&id).map(|(hotkey, _)| hotkey);
+        }
+        let hotkey = *hotkey;
+        self.insert(hotkey, enabled);
+        None
+    }
+
+    /// Pauses the manager, the grabbed hotkeys should then be released and parked as enabled.
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Resumes the manager, returns the enabled hotkeys that should be grabbed again.
+    pub fn resume(&mut self) -> Vec<HotKey> {
+        self.paused = false;
+        let enabled = self.hotkeys.values().filter(|(_, enabled)| *enabled);
+        let enabled: Vec<HotKey> = enabled.map(|&(hotkey, _)| hotkey).collect();
+        for hotkey in &enabled {
+            self.hotkeys.remove(&hotkey.id());
+        }
+        enabled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use keyboard_types::{Code, Modifiers};
+
+    use super::ParkedHotKeys;
+    use crate::hotkey::HotKey;
+
+    #[test]
+    fn disabled_hotkeys_stay_parked_on_resume() {
+        let a = HotKey::new(Some(Modifiers::CONTROL), Code::KeyA);
+        let b = HotKey::new(Some(Modifiers::CONTROL), Code::KeyB);
+        let mut parked = ParkedHotKeys::default();
+
+        parked.insert(a, false);
+        assert_eq!(parked.set_enabled(a.id(), true), Some(a));
+        assert!(!parked.contains(a.id()));
+
+        parked.pause();
+        parked.insert(a, true);
+        parked.insert(b, true);
+        // enabling or disabling while paused doesn't grab anything
+        assert_eq!(parked.set_enabled(b.id(), false), None);
+        assert_eq!(parked.set_enabled(b.id(), true), None);
+        assert_eq!(parked.set_enabled(a.id(), false), None);
+        assert_eq!(parked.hotkeys().count(), 2);
+
+        assert_eq!(parked.resume(), vec![b]);
+        assert!(!parked.is_paused());
+        assert_eq!(parked.hotkeys().collect::<Vec<_>>(), vec![a]);
+        assert_eq!(parked.remove(a.id()), Some((a, false)));
+    }
+}
@@ -2,25 +2,60 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use std::ptr;
+use std::{
+    collections::BTreeMap,
+    ptr,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
 
 use keyboard_types::{Code, Modifiers};
 use windows_sys::Win32::{
-    Foundation::{HWND, LPARAM, LRESULT, WPARAM},
+    Foundation::{GetLastError, ERROR_HOTKEY_ALREADY_REGISTERED, HWND, LPARAM, LRESULT, WPARAM},
     UI::{
         Input::KeyboardAndMouse::*,
         WindowsAndMessaging::{
-            CreateWindowExW, DefWindowProcW, DestroyWindow, RegisterClassW, CW_USEDEFAULT, HMENU,
-            WM_HOTKEY, WNDCLASSW, WS_EX_LAYERED, WS_EX_NOACTIVATE, WS_EX_TOOLWINDOW,
-            WS_EX_TRANSPARENT, WS_OVERLAPPED,
+            CreateWindowExW, DefWindowProcW, DestroyWindow, GetWindowLongPtrW, KillTimer,
+            PostMessageW, RegisterClassW, SetTimer, SetWindowLongPtrW, CW_USEDEFAULT,
+            GWLP_USERDATA, HMENU, WM_APP, WM_HOTKEY, WM_TIMER, WNDCLASSW, WS_EX_LAYERED,
+            WS_EX_NOACTIVATE, WS_EX_TOOLWINDOW, WS_EX_TRANSPARENT, WS_OVERLAPPED,
         },
     },
 };
 
-use crate::{hotkey::HotKey, GlobalHotKeyEvent};
+use crate::{
+    activation::Activations,
+    hotkey::{HotKey, HotKeySequence, ModifierSides, SequenceTracker, Trigger},
+    platform_impl::ParkedHotKeys,
+    FailureReason, GlobalHotKeyEvent,
+};
+
+/// Posted by the thread waiting for a hotkey to be released, with the same `WPARAM` as the `WM_HOTKEY` message.
+const WM_HOTKEY_RELEASED: u32 = WM_APP + 1;
+const SEQUENCE_TIMER_ID: usize = 1;
 
 pub struct GlobalHotKeyManager {
     hwnd: isize,
+    state: Box<Mutex<WindowState>>,
+}
+
+/// State shared with [`global_hotkey_proc`] through `GWLP_USERDATA`.
+#[derive(Default)]
+struct WindowState {
+    /// The grabbed hotkeys, keyed by their id.
+    hotkeys: BTreeMap<u32, HotKey>,
+    /// The registered hotkeys which aren't grabbed, because they are disabled or the manager is paused.
+    parked: ParkedHotKeys,
+    /// The ids of the hotkeys and sequence strokes using each `RegisterHotKey` registration,
+    /// keyed by the registration's id, see [`grab_id`].
+    grabs: BTreeMap<u32, Vec<u32>>,
+    sequences: SequenceTracker,
+    sequence_grabs: Vec<HotKey>,
+    /// Ids of the hotkeys and sequence strokes that fired for a `RegisterHotKey`
+    /// registration which hasn't been released yet.
+    pressed: BTreeMap<u32, Vec<u32>>,
+    /// The options of the hotkeys, which turn the reported presses into the emitted events.
+    activations: Arc<Activations>,
 }
 
 impl Drop for GlobalHotKeyManager {
@@ -30,7 +65,7 @@ impl Drop for GlobalHotKeyManager {
 }
 
 impl GlobalHotKeyManager {
-    pub fn new() -> crate::Result<Self> {
+    pub fn new(activations: Arc<Activations>) -> crate::Result<Self> {
         let class_name = encode_wide("global_hotkey_app");
         unsafe {
             let hinstance = get_instance_handle();
@@ -70,88 +105,500 @@ impl GlobalHotKeyManager {
                 return Err(crate::Error::OsError(std::io::Error::last_os_error()));
             }
 
-            Ok(Self { hwnd })
+            let state = Box::new(Mutex::new(WindowState {
+                activations,
+                ..Default::default()
+            }));
+            SetWindowLongPtrW(hwnd, GWLP_USERDATA, &*state as *const _ as _);
+
+            Ok(Self { hwnd, state })
         }
     }
 
     pub fn register(&self, hotkey: HotKey) -> crate::Result<()> {
-        let mut mods = MOD_NOREPEAT;
-        if hotkey.mods.contains(Modifiers::SHIFT) {
-            mods |= MOD_SHIFT;
+        let mut state = self.state.lock().unwrap();
+        if state.hotkeys.contains_key(&hotkey.id()) || state.parked.contains(hotkey.id()) {
+            return Err(crate::Error::AlreadyRegistered(hotkey));
         }
-        if hotkey.mods.intersects(Modifiers::SUPER | Modifiers::META) {
-            mods |= MOD_WIN;
+
+        grab(self.hwnd, &mut state.grabs, hotkey)?;
+        // hotkeys registered while paused are grabbed on resume,
+        // grabbing them once still reports a combination that can't be grabbed
+        if state.parked.is_paused() {
+            ungrab(self.hwnd, &mut state.grabs, hotkey)?;
+            state.parked.insert(hotkey, true);
+            return Ok(());
         }
-        if hotkey.mods.contains(Modifiers::ALT) {
-            mods |= MOD_ALT;
+        state.hotkeys.insert(hotkey.id(), hotkey);
+
+        Ok(())
+    }
+
+    pub fn unregister(&self, hotkey: HotKey) -> crate::Result<()> {
+        let mut state = self.state.lock().unwrap();
+        if state.parked.remove(hotkey.id()).is_some() {
+            return Ok(());
         }
-        if hotkey.mods.contains(Modifiers::CONTROL) {
-            mods |= MOD_CONTROL;
+        if state.hotkeys.remove(&hotkey.id()).is_none() {
+            return Err(crate::Error::FailedToUnRegister {
+                hotkey,
+                reason: FailureReason::NotRegistered,
+            });
         }
 
-        // get key scan code
-        match key_to_vk(&hotkey.key) {
-            Some(vk_code) => {
-                let result =
-                    unsafe { RegisterHotKey(self.hwnd, hotkey.id() as _, mods, vk_code as _) };
-                if result == 0 {
-                    return Err(crate::Error::AlreadyRegistered(hotkey));
-                }
-            }
-            _ => {
-                return Err(crate::Error::FailedToRegister(format!(
-                    "Unable to register hotkey (unknown VKCode for this key: {}).",
-                    hotkey.key
-                )))
+        ungrab(self.hwnd, &mut state.grabs, hotkey)
+    }
+
+    pub fn rebind(&self, old: HotKey, new: HotKey) -> crate::Result<()> {
+        let mut state = self.state.lock().unwrap();
+        if !state.hotkeys.contains_key(&old.id()) && !state.parked.contains(old.id()) {
+            return Err(crate::Error::FailedToUnRegister {
+                hotkey: old,
+                reason: FailureReason::NotRegistered,
+            });
+        }
+        if state.hotkeys.contains_key(&new.id()) || state.parked.contains(new.id()) {
+            return Err(crate::Error::AlreadyRegistered(new));
+        }
+        // a parked hotkey isn't grabbed, the new one takes its place until it's enabled or resumed,
+        // after a grab probe so an unsupported or taken hotkey fails now instead of on resume
+        if state.parked.contains(old.id()) {
+            grab(self.hwnd, &mut state.grabs, new)?;
+            ungrab(self.hwnd, &mut state.grabs, new)?;
+            if let Some((_, enabled)) = state.parked.remove(old.id()) {
+                state.parked.insert(new, enabled);
             }
+            return Ok(());
         }
 
+        // grab the new hotkey first, so the old one stays registered if that fails
+        grab(self.hwnd, &mut state.grabs, new)?;
+        if let Err(e) = ungrab(self.hwnd, &mut state.grabs, old) {
+            let _ = ungrab(self.hwnd, &mut state.grabs, new);
+            let _ = grab(self.hwnd, &mut state.grabs, old);
+            return Err(e);
+        }
+        state.hotkeys.remove(&old.id());
+        state.hotkeys.insert(new.id(), new);
         Ok(())
     }
 
-    pub fn unregister(&self, hotkey: HotKey) -> crate::Result<()> {
-        let result = unsafe { UnregisterHotKey(self.hwnd, hotkey.id() as _) };
-        if result == 0 {
-            return Err(crate::Error::FailedToUnRegister(hotkey));
+    pub fn set_enabled(&self, hotkey: HotKey, enabled: bool) -> crate::Result<()> {
+        let mut state = self.state.lock().unwrap();
+        let id = hotkey.id();
+        if state.hotkeys.contains_key(&id) {
+            if !enabled {
+                state.hotkeys.remove(&id);
+                state.parked.insert(hotkey, false);
+                return ungrab(self.hwnd, &mut state.grabs, hotkey);
+            }
+            return Ok(());
+        }
+        if !state.parked.contains(id) {
+            let reason = FailureReason::NotRegistered;
+            return Err(if enabled {
+                crate::Error::FailedToRegister { hotkey, reason }
+            } else {
+                crate::Error::FailedToUnRegister { hotkey, reason }
+            });
+        }
+
+        if let Some(hotkey) = state.parked.set_enabled(id, enabled) {
+            if let Err(e) = grab(self.hwnd, &mut state.grabs, hotkey) {
+                state.parked.insert(hotkey, false);
+                return Err(e);
+            }
+            state.hotkeys.insert(id, hotkey);
         }
         Ok(())
     }
 
+    pub fn pause_all(&self) -> crate::Result<()> {
+        let mut state = self.state.lock().unwrap();
+        if state.parked.is_paused() {
+            return Ok(());
+        }
+
+        state.parked.pause();
+        let mut result = Ok(());
+        for hotkey in std::mem::take(&mut state.hotkeys).into_values() {
+            state.parked.insert(hotkey, true);
+            let ungrabbed = ungrab(self.hwnd, &mut state.grabs, hotkey);
+            if result.is_ok() {
+                result = ungrabbed;
+            }
+        }
+        sync_sequence_grabs(self.hwnd, &mut state);
+        result
+    }
+
+    pub fn resume_all(&self) -> crate::Result<()> {
+        let mut state = self.state.lock().unwrap();
+        if !state.parked.is_paused() {
+            return Ok(());
+        }
+
+        // hotkeys that can't be grabbed again stay parked as disabled
+        let mut result = Ok(());
+        for hotkey in state.parked.resume() {
+            match grab(self.hwnd, &mut state.grabs, hotkey) {
+                Ok(()) => {
+                    state.hotkeys.insert(hotkey.id(), hotkey);
+                }
+                Err(e) => {
+                    state.parked.insert(hotkey, false);
+                    if result.is_ok() {
+                        result = Err(e);
+                    }
+                }
+            }
+        }
+        sync_sequence_grabs(self.hwnd, &mut state);
+        result
+    }
+
     pub fn register_all(&self, hotkeys: &[HotKey]) -> crate::Result<()> {
-        for hotkey in hotkeys {
-            self.register(*hotkey)?;
+        for (i, hotkey) in hotkeys.iter().enumerate() {
+            if let Err(e) = self.register(*hotkey) {
+                // all or nothing, release what this batch already registered
+                for hotkey in hotkeys[..i].iter().rev() {
+                    let _ = self.unregister(*hotkey);
+                }
+                return Err(e);
+            }
         }
         Ok(())
     }
 
+    pub fn register_each(&self, hotkeys: &[HotKey]) -> Vec<(HotKey, crate::Result<()>)> {
+        hotkeys
+            .iter()
+            .map(|&hotkey| (hotkey, self.register(hotkey)))
+            .collect()
+    }
+
     pub fn unregister_all(&self, hotkeys: &[HotKey]) -> crate::Result<()> {
         for hotkey in hotkeys {
             self.unregister(*hotkey)?;
         }
         Ok(())
     }
+
+    pub fn register_sequence(&self, sequence: HotKeySequence) -> crate::Result<()> {
+        if let Some(stroke) = sequence
+            .strokes()
+            .iter()
+            .find(|s| trigger_to_vk(s.trigger()).is_none())
+        {
+            return Err(crate::Error::FailedToRegister {
+                hotkey: *stroke,
+                reason: FailureReason::UnsupportedKey,
+            });
+        }
+
+        let mut state = self.state.lock().unwrap();
+        let first = sequence.strokes()[0];
+        let id = sequence.id();
+        if !state.sequences.register(sequence.clone()) {
+            return Err(crate::Error::SequenceAlreadyRegistered(sequence));
+        }
+        sync_sequence_grabs(self.hwnd, &mut state);
+
+        // nothing is grabbed while paused, the sequence is grabbed on resume
+        if !state.parked.is_paused() && !state.sequence_grabs.contains(&first) {
+            state.sequences.unregister(id);
+            sync_sequence_grabs(self.hwnd, &mut state);
+            return Err(crate::Error::FailedToRegister {
+                hotkey: first,
+                reason: FailureReason::GrabbedByOtherClient,
+            });
+        }
+
+        Ok(())
+    }
+
+    pub fn unregister_sequence(&self, sequence: &HotKeySequence) -> crate::Result<()> {
+        let mut state = self.state.lock().unwrap();
+        if state.sequences.unregister(sequence.id()).is_none() {
+            return Err(crate::Error::FailedToUnRegister {
+                hotkey: sequence.strokes()[0],
+                reason: FailureReason::NotRegistered,
+            });
+        }
+        sync_sequence_grabs(self.hwnd, &mut state);
+        schedule_sequence_timer(self.hwnd, &state);
+        Ok(())
+    }
+
+    pub fn hotkeys(&self) -> Vec<HotKey> {
+        let state = self.state.lock().unwrap();
+        state
+            .hotkeys
+            .values()
+            .copied()
+            .chain(state.parked.hotkeys())
+            .collect()
+    }
 }
+
+/// Registers the hotkey with the OS, unless it is already registered for another user.
+///
+/// `RegisterHotKey` doesn't know about modifier sides, so hotkeys that only differ
+/// in their sides share a registration, identified by the id of the hotkey without sides.
+fn grab(hwnd: HWND, grabs: &mut BTreeMap<u32, Vec<u32>>, hotkey: HotKey) -> crate::Result<()> {
+    // modifier-only, mouse button and wheel hotkeys can't be registered with `RegisterHotKey`
+    if let Trigger::ModifiersOnly | Trigger::Mouse(_) | Trigger::Wheel(_) = hotkey.trigger() {
+        return Err(crate::Error::FailedToRegister {
+            hotkey,
+            reason: FailureReason::UnsupportedKey,
+        });
+    }
+    if hotkey
+        .mods
+        .intersects(Modifiers::HYPER | Modifiers::CAPS_LOCK)
+    {
+        return Err(crate::Error::FailedToRegister {
+            hotkey,
+            reason: FailureReason::UnsupportedModifiers,
+        });
+    }
+
+    // repeats aren't disabled with `MOD_NOREPEAT` so they can be reported as `HotKeyState::Repeat`
+    let mut mods = 0;
+    if hotkey.mods.contains(Modifiers::SHIFT) {
+        mods |= MOD_SHIFT;
+    }
+    if hotkey.mods.intersects(Modifiers::SUPER | Modifiers::META) {
+        mods |= MOD_WIN;
+    }
+    if hotkey.mods.contains(Modifiers::ALT) {
+        mods |= MOD_ALT;
+    }
+    if hotkey.mods.contains(Modifiers::CONTROL) {
+        mods |= MOD_CONTROL;
+    }
+    // AltGr is reported as Ctrl+Alt on Windows
+    if hotkey.mods.contains(Modifiers::ALT_GRAPH) {
+        mods |= MOD_CONTROL | MOD_ALT;
+    }
+
+    let Some(vk_code) = trigger_to_vk(hotkey.trigger()) else {
+        return Err(crate::Error::FailedToRegister {
+            hotkey,
+            reason: FailureReason::UnsupportedKey,
+        });
+    };
+
+    // hotkeys that only differ in their sides or in modifiers Windows doesn't tell apart,
+    // like Super and Meta, share the registration
+    let id = grab_id(mods, vk_code as u32);
+    if !grabs.contains_key(&id) {
+        let result = unsafe { RegisterHotKey(hwnd, id as _, mods, vk_code as _) };
+        if result == 0 {
+            let reason = match unsafe { GetLastError() } {
+                ERROR_HOTKEY_ALREADY_REGISTERED => FailureReason::GrabbedByOtherClient,
+                code => FailureReason::Os(code as _),
+            };
+            return Err(crate::Error::FailedToRegister { hotkey, reason });
+        }
+    }
+
+    grabs.entry(id).or_default().push(hotkey.id());
+    Ok(())
+}
+
+fn ungrab(hwnd: HWND, grabs: &mut BTreeMap<u32, Vec<u32>>, hotkey: HotKey) -> crate::Result<()> {
+    let Some((&id, users)) = grabs
+        .iter_mut()
+        .find(|(_, users)| users.contains(&hotkey.id()))
+    else {
+        return Ok(());
+    };
+
+    if let Some(i) = users.iter().position(|&user| user == hotkey.id()) {
+        users.remove(i);
+    }
+    if users.is_empty() {
+        grabs.remove(&id);
+        let result = unsafe { UnregisterHotKey(hwnd, id as _) };
+        if result == 0 {
+            return Err(crate::Error::FailedToUnRegister {
+                hotkey,
+                reason: FailureReason::Os(unsafe { GetLastError() } as _),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Returns the id of the `RegisterHotKey` registration of these modifiers and virtual key,
+/// within the `0x0000..=0xBFFF` range of application hotkey ids.
+fn grab_id(mods: u32, vk_code: u32) -> u32 {
+    (mods << 8) | vk_code
+}
+
+/// Grabs the strokes the sequences are currently waiting for and releases the others.
+fn sync_sequence_grabs(hwnd: HWND, state: &mut WindowState) {
+    // nothing is grabbed while paused
+    let wanted = if state.parked.is_paused() {
+        Vec::new()
+    } else {
+        state.sequences.grabbed_strokes()
+    };
+    let WindowState {
+        grabs,
+        sequence_grabs,
+        ..
+    } = state;
+
+    sequence_grabs.retain(|stroke| {
+        let keep = wanted.contains(stroke);
+        if !keep {
+            let _ = ungrab(hwnd, grabs, *stroke);
+        }
+        keep
+    });
+
+    for stroke in wanted {
+        if !sequence_grabs.contains(&stroke) && grab(hwnd, grabs, stroke).is_ok() {
+            sequence_grabs.push(stroke);
+        }
+    }
+}
+
+/// Returns the sides of the modifier keys that are currently held down.
+fn pressed_sides() -> ModifierSides {
+    [
+        (ModifierSides::SHIFT_LEFT, VK_LSHIFT),
+        (ModifierSides::SHIFT_RIGHT, VK_RSHIFT),
+        (ModifierSides::CONTROL_LEFT, VK_LCONTROL),
+        (ModifierSides::CONTROL_RIGHT, VK_RCONTROL),
+        (ModifierSides::ALT_LEFT, VK_LMENU),
+        (ModifierSides::ALT_RIGHT, VK_RMENU),
+        (ModifierSides::SUPER_LEFT, VK_LWIN),
+        (ModifierSides::SUPER_RIGHT, VK_RWIN),
+    ]
+    .into_iter()
+    .filter(|(_, vk)| unsafe { GetAsyncKeyState(*vk as i32) } as u16 & 0x8000 != 0)
+    .fold(ModifierSides::empty(), |sides, (side, _)| sides | side)
+}
+
+fn schedule_sequence_timer(hwnd: HWND, state: &WindowState) {
+    match state.sequences.deadline() {
+        Some(deadline) => {
+            let timeout = deadline.saturating_duration_since(Instant::now());
+            unsafe {
+                SetTimer(
+                    hwnd,
+                    SEQUENCE_TIMER_ID,
+                    timeout.as_millis() as u32 + 1,
+                    None,
+                )
+            };
+        }
+        None => unsafe {
+            KillTimer(hwnd, SEQUENCE_TIMER_ID);
+        },
+    }
+}
+
 unsafe extern "system" fn global_hotkey_proc(
     hwnd: HWND,
     msg: u32,
     wparam: WPARAM,
     lparam: LPARAM,
 ) -> LRESULT {
-    if msg == WM_HOTKEY {
-        GlobalHotKeyEvent::send(GlobalHotKeyEvent {
-            id: wparam as _,
-            state: crate::HotKeyState::Pressed,
-        });
-        std::thread::spawn(move || loop {
-            let state = GetAsyncKeyState(HIWORD(lparam as u32) as i32);
-            if state == 0 {
-                GlobalHotKeyEvent::send(GlobalHotKeyEvent {
-                    id: wparam as _,
-                    state: crate::HotKeyState::Released,
+    let state = GetWindowLongPtrW(hwnd, GWLP_USERDATA) as *const Mutex<WindowState>;
+    if state.is_null() {
+        return DefWindowProcW(hwnd, msg, wparam, lparam);
+    }
+
+    let mut events = Vec::new();
+    match msg {
+        WM_HOTKEY => {
+            let mut state = (*state).lock().unwrap();
+            let id = wparam as u32;
+            if let Some(pressed) = state.pressed.get(&id) {
+                // a key repeat while the hotkey is held
+                for &id in pressed.iter().filter(|id| state.hotkeys.contains_key(id)) {
+                    events.push(GlobalHotKeyEvent::new(id, crate::HotKeyState::Repeat(0)));
+                }
+            } else {
+                let sides = pressed_sides();
+                let users = state.grabs.get(&id).cloned().unwrap_or_default();
+                let matches = |hotkey: &HotKey| {
+                    users.contains(&hotkey.id()) && hotkey.sides().satisfied_by(sides)
+                };
+
+                let mut pressed: Vec<u32> = state
+                    .hotkeys
+                    .values()
+                    .filter(|h| matches(h))
+                    .map(|h| h.id())
+                    .collect();
+                for &id in &pressed {
+                    events.push(GlobalHotKeyEvent::new(id, crate::HotKeyState::Pressed));
+                }
+
+                let stroke = state
+                    .sequence_grabs
+                    .iter()
+                    .filter(|s| matches(s))
+                    // prefer `RCtrl+K` over `Ctrl+K` when both are waited for
+                    .max_by_key(|s| s.sides().bits().count_ones())
+                    .map(|s| s.id());
+                if let Some(stroke) = stroke {
+                    if !pressed.contains(&stroke) {
+                        pressed.push(stroke);
+                    }
+                    if let Some(id) = state.sequences.press(stroke, Instant::now()) {
+                        events.push(GlobalHotKeyEvent::new(id, crate::HotKeyState::Pressed));
+                    }
+                    sync_sequence_grabs(hwnd, &mut state);
+                    schedule_sequence_timer(hwnd, &state);
+                }
+                state.pressed.insert(id, pressed);
+
+                std::thread::spawn(move || loop {
+                    let state = GetAsyncKeyState(HIWORD(lparam as u32) as i32);
+                    if state == 0 {
+                        PostMessageW(hwnd, WM_HOTKEY_RELEASED, wparam, lparam);
+                        break;
+                    }
                 });
-                break;
             }
-        });
+        }
+        WM_HOTKEY_RELEASED => {
+            let mut state = (*state).lock().unwrap();
+            let pressed = state.pressed.remove(&(wparam as u32)).unwrap_or_default();
+
+            for id in pressed {
+                if state.hotkeys.contains_key(&id) {
+                    events.push(GlobalHotKeyEvent::new(id, crate::HotKeyState::Released));
+                }
+
+                if let Some(id) = state.sequences.release(id) {
+                    events.push(GlobalHotKeyEvent::new(id, crate::HotKeyState::Released));
+                    sync_sequence_grabs(hwnd, &mut state);
+                }
+            }
+        }
+        WM_TIMER if wparam == SEQUENCE_TIMER_ID => {
+            let mut state = (*state).lock().unwrap();
+            if state.sequences.expire(Instant::now()) {
+                sync_sequence_grabs(hwnd, &mut state);
+            }
+            schedule_sequence_timer(hwnd, &state);
+        }
+        _ => {}
+    }
+
+    // sent after releasing the lock so event handlers can use the manager
+    if !events.is_empty() {
+        let activations = (*state).lock().unwrap().activations.clone();
+        for event in events {
+            activations.send(event);
+        }
     }
 
     DefWindowProcW(hwnd, msg, wparam, lparam)
@@ -184,6 +631,91 @@ pub fn get_instance_handle() -> windows_sys::Win32::Foundation::HMODULE {
     unsafe { &__ImageBase as *const _ as _ }
 }
 
+fn trigger_to_vk(trigger: Trigger) -> Option<VIRTUAL_KEY> {
+    match trigger {
+        // the VKCodes of the alphanumeric keys depend on the layout,
+        // use the VKCode of the key at this position instead
+        Trigger::Key(key) => match key_to_scancode(key) {
+            Some(scancode) => match unsafe { MapVirtualKeyW(scancode, MAPVK_VSC_TO_VK) } {
+                0 => key_to_vk(&key),
+                vk => Some(vk as _),
+            },
+            None => key_to_vk(&key),
+        },
+        Trigger::Character(c) => {
+            let mut utf16 = [0; 2];
+            let vk = match c.encode_utf16(&mut utf16) {
+                [c] => unsafe { VkKeyScanW(*c) },
+                _ => -1,
+            };
+            if vk == -1 {
+                Trigger::fallback_key(c).and_then(|key| trigger_to_vk(Trigger::Key(key)))
+            } else {
+                // the low byte is the VKCode, the high byte the shift state needed to type the character
+                Some((vk & 0xff) as _)
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Returns the scancode of the alphanumeric keys, whose VKCode depends on the layout.
+fn key_to_scancode(key: Code) -> Option<u32> {
+    Some(match key {
+        Code::Backquote => 0x29,
+        Code::Digit1 => 0x02,
+        Code::Digit2 => 0x03,
+        Code::Digit3 => 0x04,
+        Code::Digit4 => 0x05,
+        Code::Digit5 => 0x06,
+        Code::Digit6 => 0x07,
+        Code::Digit7 => 0x08,
+        Code::Digit8 => 0x09,
+        Code::Digit9 => 0x0a,
+        Code::Digit0 => 0x0b,
+        Code::Minus => 0x0c,
+        Code::Equal => 0x0d,
+        Code::KeyQ => 0x10,
+        Code::KeyW => 0x11,
+        Code::KeyE => 0x12,
+        Code::KeyR => 0x13,
+        Code::KeyT => 0x14,
+        Code::KeyY => 0x15,
+        Code::KeyU => 0x16,
+        Code::KeyI => 0x17,
+        Code::KeyO => 0x18,
+        Code::KeyP => 0x19,
+        Code::BracketLeft => 0x1a,
+        Code::BracketRight => 0x1b,
+        Code::KeyA => 0x1e,
+        Code::KeyS => 0x1f,
+        Code::KeyD => 0x20,
+        Code::KeyF => 0x21,
+        Code::KeyG => 0x22,
+        Code::KeyH => 0x23,
+        Code::KeyJ => 0x24,
+        Code::KeyK => 0x25,
+        Code::KeyL => 0x26,
+        Code::Semicolon => 0x27,
+        Code::Quote => 0x28,
+        Code::Backslash => 0x2b,
+        Code::KeyZ => 0x2c,
+        Code::KeyX => 0x2d,
+        Code::KeyC => 0x2e,
+        Code::KeyV => 0x2f,
+        Code::KeyB => 0x30,
+        Code::KeyN => 0x31,
+        Code::KeyM => 0x32,
+        Code::Comma => 0x33,
+        Code::Period => 0x34,
+        Code::Slash => 0x35,
+        Code::IntlBackslash => 0x56,
+        Code::IntlRo => 0x73,
+        Code::IntlYen => 0x7d,
+        _ => return None,
+    })
+}
+
 // used to build accelerators table from Key
 fn key_to_vk(key: &Code) -> Option<VIRTUAL_KEY> {
     Some(match key {
@@ -290,13 +822,41 @@ fn key_to_vk(key: &Code) -> Option<VIRTUAL_KEY> {
         Code::NumpadDecimal => VK_DECIMAL,
         Code::NumpadDivide => VK_DIVIDE,
         Code::NumpadEnter => VK_RETURN,
-        Code::NumpadEqual => VK_E,
+        Code::NumpadEqual => VK_OEM_NEC_EQUAL,
         Code::NumpadMultiply => VK_MULTIPLY,
         Code::NumpadSubtract => VK_SUBTRACT,
         Code::ScrollLock => VK_SCROLL,
         Code::AudioVolumeDown => VK_VOLUME_DOWN,
         Code::AudioVolumeUp => VK_VOLUME_UP,
         Code::AudioVolumeMute => VK_VOLUME_MUTE,
+        Code::MediaPlayPause => VK_MEDIA_PLAY_PAUSE,
+        Code::MediaStop => VK_MEDIA_STOP,
+        Code::MediaTrackNext => VK_MEDIA_NEXT_TRACK,
+        Code::MediaTrackPrevious => VK_MEDIA_PREV_TRACK,
+        Code::ContextMenu => VK_APPS,
+        Code::Convert => VK_CONVERT,
+        Code::NonConvert => VK_NONCONVERT,
+        // `Lang1`, the Hangul key, sends the same virtual key
+        Code::KanaMode => VK_KANA,
+        Code::Lang2 => VK_HANJA,
+        Code::Help => VK_HELP,
+        Code::Pause => VK_PAUSE,
+        Code::Select => VK_SELECT,
+        Code::NumpadComma => VK_SEPARATOR,
+        Code::BrowserBack => VK_BROWSER_BACK,
+        Code::BrowserFavorites => VK_BROWSER_FAVORITES,
+        Code::BrowserForward => VK_BROWSER_FORWARD,
+        Code::BrowserHome => VK_BROWSER_HOME,
+        Code::BrowserRefresh => VK_BROWSER_REFRESH,
+        Code::BrowserSearch => VK_BROWSER_SEARCH,
+        Code::BrowserStop => VK_BROWSER_STOP,
+        Code::LaunchApp1 => VK_LAUNCH_APP1,
+        Code::LaunchApp2 => VK_LAUNCH_APP2,
+        Code::LaunchMail => VK_LAUNCH_MAIL,
+        Code::MediaSelect => VK_LAUNCH_MEDIA_SELECT,
+        Code::MediaPlay => VK_PLAY,
+        Code::Sleep => VK_SLEEP,
+        Code::ZoomToggle => VK_ZOOM,
         _ => return None,
     })
 }
@@ -2,34 +2,67 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use std::{collections::BTreeMap, ptr};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    os::raw::c_int,
+    ptr,
+    sync::{
+        atomic::{AtomicU8, Ordering},
+        Arc, Mutex,
+    },
+    time::Instant,
+};
 
 use crossbeam_channel::{unbounded, Receiver, Sender};
 use keyboard_types::{Code, Modifiers};
 use x11_dl::{
     keysym,
-    xlib::{self, Xlib, _XDisplay},
+    xinput2::{self, XInput2},
+    xlib::{self, _XDisplay, Xlib},
 };
 
-use crate::{hotkey::HotKey, GlobalHotKeyEvent};
+use crate::{
+    activation::Activations,
+    hotkey::{
+        HotKey, HotKeySequence, ModifierSides, MouseButton, SequenceTracker, Trigger,
+        WheelDirection,
+    },
+    platform_impl::ParkedHotKeys,
+    FailureReason, GlobalHotKeyEvent, HotKeyState,
+};
 
 enum ThreadMessage {
     RegisterHotKey(HotKey, Sender<crate::Result<()>>),
     RegisterHotKeys(Vec<HotKey>, Sender<crate::Result<()>>),
+    RegisterEach(Vec<HotKey>, Sender<Vec<crate::Result<()>>>),
     UnRegisterHotKey(HotKey, Sender<crate::Result<()>>),
     UnRegisterHotKeys(Vec<HotKey>, Sender<crate::Result<()>>),
+    Rebind(HotKey, HotKey, Sender<crate::Result<()>>),
+    RegisterSequence(HotKeySequence, Sender<crate::Result<()>>),
+    UnRegisterSequence(HotKeySequence, Sender<crate::Result<()>>),
+    SetEnabled(HotKey, bool, Sender<crate::Result<()>>),
+    PauseAll(Sender<crate::Result<()>>),
+    ResumeAll(Sender<crate::Result<()>>),
     DropThread,
 }
 
 pub struct GlobalHotKeyManager {
     thread_tx: Sender<ThreadMessage>,
+    /// A copy of the registered hotkeys kept by the events thread, which can be read
+    /// without waiting for it, like from the event handler it calls.
+    registry: Arc<Mutex<Vec<HotKey>>>,
 }
 
 impl GlobalHotKeyManager {
-    pub fn new() -> crate::Result<Self> {
+    pub fn new(activations: Arc<Activations>) -> crate::Result<Self> {
         let (thread_tx, thread_rx) = unbounded();
-        std::thread::spawn(|| events_processor(thread_rx));
-        Ok(Self { thread_tx })
+        let registry = Arc::new(Mutex::new(Vec::new()));
+        let thread_registry = registry.clone();
+        std::thread::spawn(|| events_processor(thread_rx, thread_registry, activations));
+        Ok(Self {
+            thread_tx,
+            registry,
+        })
     }
 
     pub fn register(&self, hotkey: HotKey) -> crate::Result<()> {
@@ -38,11 +71,11 @@ impl GlobalHotKeyManager {
             .thread_tx
             .send(ThreadMessage::RegisterHotKey(hotkey, tx));
 
-        if let Ok(result) = rx.recv() {
-            result?;
-        }
-
-        Ok(())
+        // the events thread is gone when the X server couldn't be reached
+        rx.recv().unwrap_or(Err(crate::Error::FailedToRegister {
+            hotkey,
+            reason: FailureReason::BackendUnavailable,
+        }))
     }
 
     pub fn unregister(&self, hotkey: HotKey) -> crate::Result<()> {
@@ -51,11 +84,10 @@ impl GlobalHotKeyManager {
             .thread_tx
             .send(ThreadMessage::UnRegisterHotKey(hotkey, tx));
 
-        if let Ok(result) = rx.recv() {
-            result?;
-        }
-
-        Ok(())
+        rx.recv().unwrap_or(Err(crate::Error::FailedToUnRegister {
+            hotkey,
+            reason: FailureReason::BackendUnavailable,
+        }))
     }
 
     pub fn register_all(&self, hotkeys: &[HotKey]) -> crate::Result<()> {
@@ -64,11 +96,36 @@ impl GlobalHotKeyManager {
             .thread_tx
             .send(ThreadMessage::RegisterHotKeys(hotkeys.to_vec(), tx));
 
-        if let Ok(result) = rx.recv() {
-            result?;
+        match (rx.recv(), hotkeys.first()) {
+            (Ok(result), _) => result,
+            (Err(_), Some(&hotkey)) => Err(crate::Error::FailedToRegister {
+                hotkey,
+                reason: FailureReason::BackendUnavailable,
+            }),
+            (Err(_), None) => Ok(()),
         }
+    }
 
-        Ok(())
+    pub fn register_each(&self, hotkeys: &[HotKey]) -> Vec<(HotKey, crate::Result<()>)> {
+        let (tx, rx) = crossbeam_channel::bounded(1);
+        let _ = self
+            .thread_tx
+            .send(ThreadMessage::RegisterEach(hotkeys.to_vec(), tx));
+
+        let results = rx.recv().unwrap_or_default();
+        let mut results = results.into_iter();
+        hotkeys
+            .iter()
+            .map(|&hotkey| {
+                let result = results
+                    .next()
+                    .unwrap_or(Err(crate::Error::FailedToRegister {
+                        hotkey,
+                        reason: FailureReason::BackendUnavailable,
+                    }));
+                (hotkey, result)
+            })
+            .collect()
     }
 
     pub fn unregister_all(&self, hotkeys: &[HotKey]) -> crate::Result<()> {
@@ -77,11 +134,80 @@ impl GlobalHotKeyManager {
             .thread_tx
             .send(ThreadMessage::UnRegisterHotKeys(hotkeys.to_vec(), tx));
 
-        if let Ok(result) = rx.recv() {
-            result?;
+        match (rx.recv(), hotkeys.first()) {
+            (Ok(result), _) => result,
+            (Err(_), Some(&hotkey)) => Err(crate::Error::FailedToUnRegister {
+                hotkey,
+                reason: FailureReason::BackendUnavailable,
+            }),
+            (Err(_), None) => Ok(()),
         }
+    }
 
-        Ok(())
+    pub fn rebind(&self, old: HotKey, new: HotKey) -> crate::Result<()> {
+        let (tx, rx) = crossbeam_channel::bounded(1);
+        let _ = self.thread_tx.send(ThreadMessage::Rebind(old, new, tx));
+
+        rx.recv().unwrap_or(Err(crate::Error::FailedToRegister {
+            hotkey: new,
+            reason: FailureReason::BackendUnavailable,
+        }))
+    }
+
+    pub fn register_sequence(&self, sequence: HotKeySequence) -> crate::Result<()> {
+        let first = sequence.strokes()[0];
+        let (tx, rx) = crossbeam_channel::bounded(1);
+        let _ = self
+            .thread_tx
+            .send(ThreadMessage::RegisterSequence(sequence, tx));
+
+        rx.recv().unwrap_or(Err(crate::Error::FailedToRegister {
+            hotkey: first,
+            reason: FailureReason::BackendUnavailable,
+        }))
+    }
+
+    pub fn unregister_sequence(&self, sequence: &HotKeySequence) -> crate::Result<()> {
+        let (tx, rx) = crossbeam_channel::bounded(1);
+        let _ = self
+            .thread_tx
+            .send(ThreadMessage::UnRegisterSequence(sequence.clone(), tx));
+
+        rx.recv().unwrap_or(Err(crate::Error::FailedToUnRegister {
+            hotkey: sequence.strokes()[0],
+            reason: FailureReason::BackendUnavailable,
+        }))
+    }
+
+    pub fn set_enabled(&self, hotkey: HotKey, enabled: bool) -> crate::Result<()> {
+        let (tx, rx) = crossbeam_channel::bounded(1);
+        let _ = self
+            .thread_tx
+            .send(ThreadMessage::SetEnabled(hotkey, enabled, tx));
+
+        rx.recv().unwrap_or(Err(crate::Error::FailedToRegister {
+            hotkey,
+            reason: FailureReason::BackendUnavailable,
+        }))
+    }
+
+    pub fn pause_all(&self) -> crate::Result<()> {
+        let (tx, rx) = crossbeam_channel::bounded(1);
+        let _ = self.thread_tx.send(ThreadMessage::PauseAll(tx));
+
+        // without the events thread nothing is grabbed
+        rx.recv().unwrap_or(Ok(()))
+    }
+
+    pub fn resume_all(&self) -> crate::Result<()> {
+        let (tx, rx) = crossbeam_channel::bounded(1);
+        let _ = self.thread_tx.send(ThreadMessage::ResumeAll(tx));
+
+        rx.recv().unwrap_or(Ok(()))
+    }
+
+    pub fn hotkeys(&self) -> Vec<HotKey> {
+        self.registry.lock().unwrap().clone()
     }
 }
 
@@ -101,95 +227,777 @@ const IGNORED_MODS: [u32; 4] = [
     xlib::Mod2Mask | xlib::LockMask,
 ];
 
-#[inline]
-fn register_hotkey(
-    xlib: &Xlib,
-    display: *mut _XDisplay,
-    root: u64,
-    hotkeys: &mut BTreeMap<u32, Vec<(u32, u32, bool)>>,
-    hotkey: HotKey,
-) -> crate::Result<()> {
-    let (modifiers, key) = (
-        modifiers_to_x11_mods(hotkey.mods),
-        keycode_to_x11_scancode(hotkey.key),
-    );
+/// A keycode grabbed with `XGrabKey` or a pointer button grabbed with `XGrabButton`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Input {
+    Key(u32),
+    Button(u32),
+}
 
-    if let Some(key) = key {
-        let keycode = unsafe { (xlib.XKeysymToKeycode)(display, key as _) };
+impl Input {
+    /// Returns the wheel direction of the buttons X11 sends for the scroll wheel.
+    fn wheel_direction(self) -> Option<WheelDirection> {
+        match self {
+            Input::Button(4) => Some(WheelDirection::Up),
+            Input::Button(5) => Some(WheelDirection::Down),
+            Input::Button(6) => Some(WheelDirection::Left),
+            Input::Button(7) => Some(WheelDirection::Right),
+            _ => None,
+        }
+    }
 
-        for m in IGNORED_MODS {
-            let result = unsafe {
-                (xlib.XGrabKey)(
+    fn grab(self, xlib: &Xlib, display: *mut _XDisplay, root: u64, modifiers: u32) {
+        unsafe {
+            match self {
+                Input::Key(keycode) => (xlib.XGrabKey)(
                     display,
                     keycode as _,
-                    modifiers | m,
+                    modifiers,
                     root,
                     0,
                     xlib::GrabModeAsync,
                     xlib::GrabModeAsync,
-                )
+                ),
+                Input::Button(button) => (xlib.XGrabButton)(
+                    display,
+                    button,
+                    modifiers,
+                    root,
+                    0,
+                    (xlib::ButtonPressMask | xlib::ButtonReleaseMask) as _,
+                    xlib::GrabModeAsync,
+                    xlib::GrabModeAsync,
+                    0,
+                    0,
+                ),
             };
+        }
+    }
 
-            if result == xlib::BadAccess as _ {
+    fn ungrab(self, xlib: &Xlib, display: *mut _XDisplay, root: u64, modifiers: u32) {
+        unsafe {
+            match self {
+                Input::Key(keycode) => (xlib.XUngrabKey)(display, keycode as _, modifiers, root),
+                Input::Button(button) => (xlib.XUngrabButton)(display, button, modifiers, root),
+            };
+        }
+    }
+}
+
+/// Reference counted key and button grabs, a grab can be shared between a [`HotKey`]
+/// and the strokes of [`HotKeySequence`]s.
+#[derive(Default)]
+struct Grabs(BTreeMap<(Input, u32), usize>);
+
+impl Grabs {
+    fn grab(
+        &mut self,
+        xlib: &Xlib,
+        display: *mut _XDisplay,
+        root: u64,
+        input: Input,
+        modifiers: u32,
+    ) -> Result<(), FailureReason> {
+        if !self.0.contains_key(&(input, modifiers)) {
+            let error = trap_errors(xlib, display, || {
                 for m in IGNORED_MODS {
-                    unsafe { (xlib.XUngrabKey)(display, keycode as _, modifiers | m, root) };
+                    input.grab(xlib, display, root, modifiers | m);
                 }
+            });
+            if let Some(code) = error {
+                for m in IGNORED_MODS {
+                    input.ungrab(xlib, display, root, modifiers | m);
+                }
+                return Err(match code {
+                    xlib::BadAccess => FailureReason::GrabbedByOtherClient,
+                    code => FailureReason::Os(code as _),
+                });
+            }
+        }
+        *self.0.entry((input, modifiers)).or_default() += 1;
+        Ok(())
+    }
 
-                return Err(crate::Error::AlreadyRegistered(hotkey));
+    fn ungrab(
+        &mut self,
+        xlib: &Xlib,
+        display: *mut _XDisplay,
+        root: u64,
+        input: Input,
+        modifiers: u32,
+    ) {
+        if let Some(count) = self.0.get_mut(&(input, modifiers)) {
+            *count -= 1;
+            if *count == 0 {
+                self.0.remove(&(input, modifiers));
+                for m in IGNORED_MODS {
+                    input.ungrab(xlib, display, root, modifiers | m);
+                }
             }
         }
+    }
+}
+
+/// The code of the first error reported by the X server while trapping errors, 0 if none.
+static TRAPPED_ERROR: AtomicU8 = AtomicU8::new(0);
+
+unsafe extern "C" fn trap_error(_: *mut xlib::Display, event: *mut xlib::XErrorEvent) -> c_int {
+    let _ = TRAPPED_ERROR.compare_exchange(
+        0,
+        (*event).error_code,
+        Ordering::Relaxed,
+        Ordering::Relaxed,
+    );
+    0
+}
+
+/// Runs `f` and returns the code of the first X error it caused.
+///
+/// X errors are reported asynchronously, so this waits for the X server to process the requests of `f`.
+fn trap_errors(xlib: &Xlib, display: *mut _XDisplay, f: impl FnOnce()) -> Option<u8> {
+    unsafe {
+        TRAPPED_ERROR.store(0, Ordering::Relaxed);
+        let previous = (xlib.XSetErrorHandler)(Some(trap_error));
+        f();
+        (xlib.XSync)(display, 0);
+        (xlib.XSetErrorHandler)(previous);
+    }
+    match TRAPPED_ERROR.swap(0, Ordering::Relaxed) {
+        0 => None,
+        code => Some(code),
+    }
+}
 
-        let entry = hotkeys.entry(keycode as _).or_default();
-        match entry.iter().find(|e| e.1 == modifiers) {
-            None => {
-                entry.push((hotkey.id(), modifiers, false));
-                Ok(())
+#[inline]
+fn hotkey_to_x11(
+    xlib: &Xlib,
+    display: *mut _XDisplay,
+    hotkey: &HotKey,
+) -> Result<(Input, u32), FailureReason> {
+    let unsupported = FailureReason::UnsupportedKey;
+    let input = match hotkey.trigger() {
+        Trigger::Key(key) => Input::Key(code_to_evdev(key).ok_or(unsupported)? + 8),
+        Trigger::Character(c) => {
+            let keycode = unsafe { (xlib.XKeysymToKeycode)(display, char_to_keysym(c) as _) };
+            match keycode {
+                0 => {
+                    let key = Trigger::fallback_key(c).and_then(code_to_evdev);
+                    Input::Key(key.ok_or(unsupported)? + 8)
+                }
+                keycode => Input::Key(keycode as u32),
             }
-            Some(_) => Err(crate::Error::AlreadyRegistered(hotkey)),
         }
-    } else {
-        Err(crate::Error::FailedToRegister(format!(
-            "Unable to register accelerator (unknown scancode for this key: {}).",
-            hotkey.key
-        )))
+        Trigger::Mouse(button) => Input::Button(match button {
+            MouseButton::Left => 1,
+            MouseButton::Middle => 2,
+            MouseButton::Right => 3,
+            MouseButton::Back => 8,
+            MouseButton::Forward => 9,
+            // X11 buttons 4 to 7 are the scroll wheel
+            button => button.number() as u32 + 4,
+        }),
+        Trigger::Wheel(direction) => Input::Button(match direction {
+            WheelDirection::Up => 4,
+            WheelDirection::Down => 5,
+            WheelDirection::Left => 6,
+            WheelDirection::Right => 7,
+        }),
+        _ => return Err(unsupported),
+    };
+    let modifiers = ModifierMasks::new(xlib, display)
+        .to_x11(hotkey.mods)
+        .ok_or(FailureReason::UnsupportedModifiers)?;
+    Ok((input, modifiers))
+}
+
+/// The keycodes of the left and right key of each modifier,
+/// used to find out which side of a modifier is held down.
+#[derive(Clone)]
+struct SideKeycodes(Vec<(ModifierSides, u8)>);
+
+impl SideKeycodes {
+    fn new(xlib: &Xlib, display: *mut _XDisplay) -> Self {
+        let keysyms = [
+            (ModifierSides::SHIFT_LEFT, keysym::XK_Shift_L),
+            (ModifierSides::SHIFT_RIGHT, keysym::XK_Shift_R),
+            (ModifierSides::CONTROL_LEFT, keysym::XK_Control_L),
+            (ModifierSides::CONTROL_RIGHT, keysym::XK_Control_R),
+            (ModifierSides::ALT_LEFT, keysym::XK_Alt_L),
+            (ModifierSides::ALT_RIGHT, keysym::XK_Alt_R),
+            (ModifierSides::SUPER_LEFT, keysym::XK_Super_L),
+            (ModifierSides::SUPER_RIGHT, keysym::XK_Super_R),
+        ];
+        Self(
+            keysyms
+                .into_iter()
+                .map(|(side, sym)| (side, unsafe { (xlib.XKeysymToKeycode)(display, sym as _) }))
+                .filter(|(_, keycode)| *keycode != 0)
+                .collect(),
+        )
+    }
+
+    /// Returns the side of the modifier key with this keycode, if it is one.
+    fn side(&self, keycode: u8) -> Option<ModifierSides> {
+        self.0
+            .iter()
+            .find(|(_, k)| *k == keycode)
+            .map(|(side, _)| *side)
+    }
+
+    /// Returns the sides of the modifier keys that are currently held down.
+    fn pressed(&self, xlib: &Xlib, display: *mut _XDisplay) -> ModifierSides {
+        let mut keys = [0 as std::os::raw::c_char; 32];
+        unsafe { (xlib.XQueryKeymap)(display, keys.as_mut_ptr()) };
+
+        self.0
+            .iter()
+            .filter(|(_, keycode)| keys[*keycode as usize / 8] as u8 & (1 << (keycode % 8)) != 0)
+            .fold(ModifierSides::empty(), |sides, (side, _)| sides | *side)
+    }
+}
+
+/// Converts the sides of the held modifier keys to the modifiers they belong to.
+fn sides_to_modifiers(sides: ModifierSides) -> Modifiers {
+    [
+        (
+            Modifiers::SHIFT,
+            ModifierSides::SHIFT_LEFT | ModifierSides::SHIFT_RIGHT,
+        ),
+        (
+            Modifiers::CONTROL,
+            ModifierSides::CONTROL_LEFT | ModifierSides::CONTROL_RIGHT,
+        ),
+        (
+            Modifiers::ALT,
+            ModifierSides::ALT_LEFT | ModifierSides::ALT_RIGHT,
+        ),
+        (
+            Modifiers::SUPER,
+            ModifierSides::SUPER_LEFT | ModifierSides::SUPER_RIGHT,
+        ),
+    ]
+    .into_iter()
+    .filter(|(_, both)| sides.intersects(*both))
+    .fold(Modifiers::empty(), |mods, (modifier, _)| mods | modifier)
+}
+
+/// Modifier-only hotkeys, which can't be grabbed with `XGrabKey`
+/// and are instead tracked using XInput2 raw key events of the root window.
+struct ModifierTaps {
+    xinput2: Option<(XInput2, i32)>,
+    side_keycodes: SideKeycodes,
+    hotkeys: Vec<HotKey>,
+    /// Keycodes of the modifier keys currently held down.
+    held: BTreeSet<u8>,
+    /// All modifier keys that have been held down together since no modifier was held.
+    chord: ModifierSides,
+    /// Set once another key or a button is pressed during the chord, or the chord triggered.
+    cancelled: bool,
+}
+
+impl ModifierTaps {
+    fn new(xlib: &Xlib, display: *mut _XDisplay, side_keycodes: SideKeycodes) -> Self {
+        let xinput2 = XInput2::open().ok().and_then(|xinput2| {
+            let mut opcode = 0;
+            let mut event = 0;
+            let mut error = 0;
+            let name = b"XInputExtension\0";
+            let (mut major, mut minor) = (2, 0);
+            unsafe {
+                ((xlib.XQueryExtension)(
+                    display,
+                    name.as_ptr() as _,
+                    &mut opcode,
+                    &mut event,
+                    &mut error,
+                ) != 0
+                    && (xinput2.XIQueryVersion)(display, &mut major, &mut minor)
+                        == xlib::Success as i32)
+                    .then_some((xinput2, opcode))
+            }
+        });
+
+        Self {
+            xinput2,
+            side_keycodes,
+            hotkeys: Vec::new(),
+            held: BTreeSet::new(),
+            chord: ModifierSides::empty(),
+            cancelled: false,
+        }
+    }
+
+    /// Starts or stops listening to raw key events, depending on whether there are modifier-only hotkeys.
+    fn select_events(&self, display: *mut _XDisplay, root: u64) {
+        if let Some((xinput2, _)) = &self.xinput2 {
+            let mut mask = [0u8; 4];
+            if !self.hotkeys.is_empty() {
+                xinput2::XISetMask(&mut mask, xinput2::XI_RawKeyPress);
+                xinput2::XISetMask(&mut mask, xinput2::XI_RawKeyRelease);
+                xinput2::XISetMask(&mut mask, xinput2::XI_RawButtonPress);
+            }
+            let mut event_mask = xinput2::XIEventMask {
+                deviceid: xinput2::XIAllMasterDevices,
+                mask_len: mask.len() as _,
+                mask: mask.as_mut_ptr(),
+            };
+            unsafe { (xinput2.XISelectEvents)(display, root, &mut event_mask, 1) };
+        }
+    }
+
+    fn register(
+        &mut self,
+        display: *mut _XDisplay,
+        root: u64,
+        hotkey: HotKey,
+    ) -> crate::Result<()> {
+        // modifier-only hotkeys need the XInput2 extension
+        if self.xinput2.is_none() {
+            return Err(crate::Error::FailedToRegister {
+                hotkey,
+                reason: FailureReason::BackendUnavailable,
+            });
+        }
+        // and at least one of Shift, Control, Alt and Super, but no other modifier
+        if hotkey.mods().is_empty()
+            || !(Modifiers::SHIFT | Modifiers::CONTROL | Modifiers::ALT | Modifiers::SUPER)
+                .contains(hotkey.mods())
+        {
+            return Err(crate::Error::FailedToRegister {
+                hotkey,
+                reason: FailureReason::UnsupportedModifiers,
+            });
+        }
+        if self.hotkeys.contains(&hotkey) {
+            return Err(crate::Error::AlreadyRegistered(hotkey));
+        }
+
+        self.hotkeys.push(hotkey);
+        if self.hotkeys.len() == 1 {
+            self.select_events(display, root);
+        }
+        Ok(())
     }
+
+    /// Removes a hotkey, returns `false` if it wasn't registered.
+    fn unregister(&mut self, display: *mut _XDisplay, root: u64, hotkey: HotKey) -> bool {
+        let len = self.hotkeys.len();
+        self.hotkeys.retain(|h| *h != hotkey);
+        if len != 0 && self.hotkeys.is_empty() {
+            self.select_events(display, root);
+        }
+        self.hotkeys.len() != len
+    }
+
+    /// Handles an XInput2 raw event and returns the ids of the hotkeys it triggered.
+    fn handle_raw_event(&mut self, evtype: i32, keycode: u8) -> Vec<u32> {
+        match (evtype, self.side_keycodes.side(keycode)) {
+            (xinput2::XI_RawKeyPress, Some(side)) => {
+                self.held.insert(keycode);
+                if !self.cancelled {
+                    self.chord |= side;
+                }
+            }
+            (xinput2::XI_RawKeyRelease, Some(_)) if self.held.remove(&keycode) => {
+                let mut triggered = Vec::new();
+                if !self.cancelled {
+                    let mods = sides_to_modifiers(self.chord);
+                    triggered = self
+                        .hotkeys
+                        .iter()
+                        .filter(|h| h.mods() == mods && h.sides().satisfied_by(self.chord))
+                        .map(|h| h.id())
+                        .collect();
+                    self.cancelled = true;
+                }
+                if self.held.is_empty() {
+                    self.chord = ModifierSides::empty();
+                    self.cancelled = false;
+                }
+                return triggered;
+            }
+            (xinput2::XI_RawKeyPress, None) | (xinput2::XI_RawButtonPress, _) => {
+                self.cancelled = true;
+            }
+            _ => {}
+        }
+        Vec::new()
+    }
+}
+
+/// A grabbed hotkey, stored under the [`Input`] it resolved to when it was registered.
+struct HotKeyEntry {
+    hotkey: HotKey,
+    /// The X11 modifier mask it resolved to when it was registered.
+    mods: u32,
+    pressed: bool,
 }
 
 #[inline]
+#[allow(clippy::too_many_arguments)]
+fn register_hotkey(
+    xlib: &Xlib,
+    display: *mut _XDisplay,
+    root: u64,
+    hotkeys: &mut BTreeMap<Input, Vec<HotKeyEntry>>,
+    grabs: &mut Grabs,
+    taps: &mut ModifierTaps,
+    parked: &mut ParkedHotKeys,
+    hotkey: HotKey,
+) -> crate::Result<()> {
+    if parked.contains(hotkey.id()) {
+        return Err(crate::Error::AlreadyRegistered(hotkey));
+    }
+    grab_hotkey(xlib, display, root, hotkeys, grabs, taps, hotkey)?;
+    // hotkeys registered while paused are grabbed on resume,
+    // grabbing them once still reports a combination that can't be grabbed
+    if parked.is_paused() {
+        unregister_hotkey(xlib, display, root, hotkeys, grabs, taps, parked, hotkey)?;
+        parked.insert(hotkey, true);
+    }
+    Ok(())
+}
+
+fn grab_hotkey(
+    xlib: &Xlib,
+    display: *mut _XDisplay,
+    root: u64,
+    hotkeys: &mut BTreeMap<Input, Vec<HotKeyEntry>>,
+    grabs: &mut Grabs,
+    taps: &mut ModifierTaps,
+    hotkey: HotKey,
+) -> crate::Result<()> {
+    if hotkey.trigger() == Trigger::ModifiersOnly {
+        return taps.register(display, root, hotkey);
+    }
+
+    let to_error = |reason| crate::Error::FailedToRegister { hotkey, reason };
+    let (input, modifiers) = hotkey_to_x11(xlib, display, &hotkey).map_err(to_error)?;
+    let entry = hotkeys.entry(input).or_default();
+    if entry
+        .iter()
+        .any(|e| e.mods == modifiers && e.hotkey.sides() == hotkey.sides())
+    {
+        return Err(crate::Error::AlreadyRegistered(hotkey));
+    }
+
+    grabs
+        .grab(xlib, display, root, input, modifiers)
+        .map_err(to_error)?;
+
+    entry.push(HotKeyEntry {
+        hotkey,
+        mods: modifiers,
+        pressed: false,
+    });
+    Ok(())
+}
+
+#[inline]
+#[allow(clippy::too_many_arguments)]
 fn unregister_hotkey(
     xlib: &Xlib,
     display: *mut _XDisplay,
     root: u64,
-    hotkeys: &mut BTreeMap<u32, Vec<(u32, u32, bool)>>,
+    hotkeys: &mut BTreeMap<Input, Vec<HotKeyEntry>>,
+    grabs: &mut Grabs,
+    taps: &mut ModifierTaps,
+    parked: &mut ParkedHotKeys,
     hotkey: HotKey,
 ) -> crate::Result<()> {
-    let (modifiers, key) = (
-        modifiers_to_x11_mods(hotkey.mods),
-        keycode_to_x11_scancode(hotkey.key),
-    );
+    if parked.remove(hotkey.id()).is_some() {
+        return Ok(());
+    }
+
+    let not_registered = crate::Error::FailedToUnRegister {
+        hotkey,
+        reason: FailureReason::NotRegistered,
+    };
+    if hotkey.trigger() == Trigger::ModifiersOnly {
+        if !taps.unregister(display, root, hotkey) {
+            return Err(not_registered);
+        }
+        return Ok(());
+    }
 
-    if let Some(key) = key {
-        let keycode = unsafe { (xlib.XKeysymToKeycode)(display, key as _) };
+    // release what was grabbed at registration, the keyboard mapping may have changed since
+    let found = hotkeys.iter_mut().find_map(|(&input, entry)| {
+        let i = entry.iter().position(|e| e.hotkey == hotkey)?;
+        Some((input, entry.remove(i).mods))
+    });
+    let Some((input, modifiers)) = found else {
+        return Err(not_registered);
+    };
+    grabs.ungrab(xlib, display, root, input, modifiers);
+    Ok(())
+}
 
-        for m in IGNORED_MODS {
-            unsafe { (xlib.XUngrabKey)(display, keycode as _, modifiers | m, root) };
+#[allow(clippy::too_many_arguments)]
+fn rebind_hotkey(
+    xlib: &Xlib,
+    display: *mut _XDisplay,
+    root: u64,
+    hotkeys: &mut BTreeMap<Input, Vec<HotKeyEntry>>,
+    grabs: &mut Grabs,
+    taps: &mut ModifierTaps,
+    parked: &mut ParkedHotKeys,
+    old: HotKey,
+    new: HotKey,
+) -> crate::Result<()> {
+    if !is_grabbed(hotkeys, taps, old) && !parked.contains(old.id()) {
+        return Err(crate::Error::FailedToUnRegister {
+            hotkey: old,
+            reason: FailureReason::NotRegistered,
+        });
+    }
+    // a parked hotkey isn't grabbed, the new one takes its place until it's enabled or resumed,
+    // after a grab probe so an unsupported or taken hotkey fails now instead of on resume
+    if parked.contains(old.id()) {
+        if is_grabbed(hotkeys, taps, new) || parked.contains(new.id()) {
+            return Err(crate::Error::AlreadyRegistered(new));
+        }
+        grab_hotkey(xlib, display, root, hotkeys, grabs, taps, new)?;
+        unregister_hotkey(xlib, display, root, hotkeys, grabs, taps, parked, new)?;
+        if let Some((_, enabled)) = parked.remove(old.id()) {
+            parked.insert(new, enabled);
         }
+        return Ok(());
+    }
 
-        let entry = hotkeys.entry(keycode as _).or_default();
-        entry.retain(|k| k.1 != modifiers);
-        Ok(())
+    // grab the new hotkey first, so the old one stays registered if that fails
+    register_hotkey(xlib, display, root, hotkeys, grabs, taps, parked, new)?;
+    if let Err(e) = unregister_hotkey(xlib, display, root, hotkeys, grabs, taps, parked, old) {
+        let _ = unregister_hotkey(xlib, display, root, hotkeys, grabs, taps, parked, new);
+        return Err(e);
+    }
+    Ok(())
+}
+
+fn is_grabbed(
+    hotkeys: &BTreeMap<Input, Vec<HotKeyEntry>>,
+    taps: &ModifierTaps,
+    hotkey: HotKey,
+) -> bool {
+    hotkeys.values().flatten().any(|e| e.hotkey == hotkey) || taps.hotkeys.contains(&hotkey)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn set_hotkey_enabled(
+    xlib: &Xlib,
+    display: *mut _XDisplay,
+    root: u64,
+    hotkeys: &mut BTreeMap<Input, Vec<HotKeyEntry>>,
+    grabs: &mut Grabs,
+    taps: &mut ModifierTaps,
+    parked: &mut ParkedHotKeys,
+    hotkey: HotKey,
+    enabled: bool,
+) -> crate::Result<()> {
+    if is_grabbed(hotkeys, taps, hotkey) {
+        if !enabled {
+            unregister_hotkey(xlib, display, root, hotkeys, grabs, taps, parked, hotkey)?;
+            parked.insert(hotkey, false);
+        }
+        return Ok(());
+    }
+    if !parked.contains(hotkey.id()) {
+        let reason = FailureReason::NotRegistered;
+        return Err(if enabled {
+            crate::Error::FailedToRegister { hotkey, reason }
+        } else {
+            crate::Error::FailedToUnRegister { hotkey, reason }
+        });
+    }
+
+    if let Some(hotkey) = parked.set_enabled(hotkey.id(), enabled) {
+        if let Err(e) = register_hotkey(xlib, display, root, hotkeys, grabs, taps, parked, hotkey) {
+            parked.insert(hotkey, false);
+            return Err(e);
+        }
+    }
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn pause_hotkeys(
+    xlib: &Xlib,
+    display: *mut _XDisplay,
+    root: u64,
+    hotkeys: &mut BTreeMap<Input, Vec<HotKeyEntry>>,
+    grabs: &mut Grabs,
+    taps: &mut ModifierTaps,
+    parked: &mut ParkedHotKeys,
+    sequences: &mut Sequences,
+) -> crate::Result<()> {
+    if parked.is_paused() {
+        return Ok(());
+    }
+
+    let grabbed = hotkeys.values().flatten().map(|e| e.hotkey);
+    let grabbed: Vec<HotKey> = grabbed.chain(taps.hotkeys.clone()).collect();
+    let mut result = Ok(());
+    for hotkey in grabbed {
+        let ungrabbed =
+            unregister_hotkey(xlib, display, root, hotkeys, grabs, taps, parked, hotkey);
+        parked.insert(hotkey, true);
+        if result.is_ok() {
+            result = ungrabbed;
+        }
+    }
+    parked.pause();
+    sequences.paused = true;
+    sync_sequence_grabs(xlib, display, root, grabs, sequences);
+    result
+}
+
+#[allow(clippy::too_many_arguments)]
+fn resume_hotkeys(
+    xlib: &Xlib,
+    display: *mut _XDisplay,
+    root: u64,
+    hotkeys: &mut BTreeMap<Input, Vec<HotKeyEntry>>,
+    grabs: &mut Grabs,
+    taps: &mut ModifierTaps,
+    parked: &mut ParkedHotKeys,
+    sequences: &mut Sequences,
+) -> crate::Result<()> {
+    if !parked.is_paused() {
+        return Ok(());
+    }
+
+    // hotkeys that can't be grabbed again stay parked as disabled
+    let mut result = Ok(());
+    for hotkey in parked.resume() {
+        if let Err(e) = register_hotkey(xlib, display, root, hotkeys, grabs, taps, parked, hotkey) {
+            parked.insert(hotkey, false);
+            if result.is_ok() {
+                result = Err(e);
+            }
+        }
+    }
+    sequences.paused = false;
+    sync_sequence_grabs(xlib, display, root, grabs, sequences);
+    result
+}
+
+#[derive(Default)]
+struct Sequences {
+    tracker: SequenceTracker,
+    //          stroke, input, mods
+    grabbed: Vec<(HotKey, Input, u32)>,
+    //                 input   stroke id
+    pressed: BTreeMap<Input, u32>,
+    /// Nothing is grabbed while the manager is paused.
+    paused: bool,
+}
+
+/// Grabs the strokes the sequences are currently waiting for and releases the others.
+fn sync_sequence_grabs(
+    xlib: &Xlib,
+    display: *mut _XDisplay,
+    root: u64,
+    grabs: &mut Grabs,
+    sequences: &mut Sequences,
+) {
+    let wanted = if sequences.paused {
+        Vec::new()
     } else {
-        Err(crate::Error::FailedToUnRegister(hotkey))
+        sequences.tracker.grabbed_strokes()
+    };
+
+    sequences.grabbed.retain(|&(stroke, input, modifiers)| {
+        let keep = wanted.contains(&stroke);
+        if !keep {
+            grabs.ungrab(xlib, display, root, input, modifiers);
+        }
+        keep
+    });
+
+    for stroke in wanted {
+        if sequences.grabbed.iter().any(|g| g.0 == stroke) {
+            continue;
+        }
+        if let Ok((input, modifiers)) = hotkey_to_x11(xlib, display, &stroke) {
+            if grabs.grab(xlib, display, root, input, modifiers).is_ok() {
+                sequences.grabbed.push((stroke, input, modifiers));
+            }
+        }
+    }
+}
+
+#[inline]
+fn register_sequence(
+    xlib: &Xlib,
+    display: *mut _XDisplay,
+    root: u64,
+    grabs: &mut Grabs,
+    sequences: &mut Sequences,
+    sequence: HotKeySequence,
+) -> crate::Result<()> {
+    // modifier-only hotkeys can't be used in a sequence, `hotkey_to_x11` rejects them
+    for &stroke in sequence.strokes() {
+        if let Err(reason) = hotkey_to_x11(xlib, display, &stroke) {
+            return Err(crate::Error::FailedToRegister {
+                hotkey: stroke,
+                reason,
+            });
+        }
+    }
+
+    let first = sequence.strokes()[0];
+    let id = sequence.id();
+    if !sequences.tracker.register(sequence.clone()) {
+        return Err(crate::Error::SequenceAlreadyRegistered(sequence));
+    }
+    sync_sequence_grabs(xlib, display, root, grabs, sequences);
+
+    // nothing is grabbed while paused, the sequence is grabbed on resume
+    if !sequences.paused && !sequences.grabbed.iter().any(|g| g.0 == first) {
+        sequences.tracker.unregister(id);
+        sync_sequence_grabs(xlib, display, root, grabs, sequences);
+        return Err(crate::Error::FailedToRegister {
+            hotkey: first,
+            reason: FailureReason::GrabbedByOtherClient,
+        });
     }
+
+    Ok(())
+}
+
+/// Copies the registered hotkeys to the registry read by [`GlobalHotKeyManager::hotkeys`],
+/// before the change is acknowledged.
+fn sync_registry(
+    registry: &Mutex<Vec<HotKey>>,
+    hotkeys: &BTreeMap<Input, Vec<HotKeyEntry>>,
+    taps: &ModifierTaps,
+    parked: &ParkedHotKeys,
+) {
+    let registered = hotkeys.values().flatten().map(|e| e.hotkey);
+    let registered = registered.chain(taps.hotkeys.iter().copied());
+    *registry.lock().unwrap() = registered.chain(parked.hotkeys()).collect();
 }
 
-fn events_processor(thread_rx: Receiver<ThreadMessage>) {
-    //                           key    id,  mods, pressed
-    let mut hotkeys = BTreeMap::<u32, Vec<(u32, u32, bool)>>::new();
+fn events_processor(
+    thread_rx: Receiver<ThreadMessage>,
+    registry: Arc<Mutex<Vec<HotKey>>>,
+    activations: Arc<Activations>,
+) {
+    let mut hotkeys = BTreeMap::<Input, Vec<HotKeyEntry>>::new();
+    let mut grabs = Grabs::default();
+    let mut sequences = Sequences::default();
+    let mut parked = ParkedHotKeys::default();
     if let Ok(xlib) = xlib::Xlib::open() {
         unsafe {
             let display = (xlib.XOpenDisplay)(ptr::null());
+            if display.is_null() {
+                #[cfg(debug_assertions)]
+                eprintln!("Failed to open the X display, maybe you are not running under X11? Other window systems on Linux are not supported by `global-hotkey` crate.");
+                return;
+            }
             let root = (xlib.XDefaultRootWindow)(display);
+            let side_keycodes = SideKeycodes::new(&xlib, display);
+            let mut taps = ModifierTaps::new(&xlib, display, side_keycodes.clone());
+            let mut masks = ModifierMasks::new(&xlib, display);
 
             // Only trigger key release at end of repeated keys
             let mut supported_rtrn: i32 = 0;
@@ -199,89 +1007,335 @@ fn events_processor(thread_rx: Receiver<ThreadMessage>) {
             let mut event: xlib::XEvent = std::mem::zeroed();
 
             loop {
-                if (xlib.XPending)(display) > 0 {
+                while (xlib.XPending)(display) > 0 {
                     (xlib.XNextEvent)(display, &mut event);
                     match event.get_type() {
-                        e @ xlib::KeyPress | e @ xlib::KeyRelease => {
-                            let keycode = event.key.keycode;
-                            // X11 sends masks for Lock keys also and we only care about the 4 below
-                            let event_mods = event.key.state
-                                & (xlib::ControlMask
-                                    | xlib::ShiftMask
-                                    | xlib::Mod4Mask
-                                    | xlib::Mod1Mask);
-
-                            if let Some(entry) = hotkeys.get_mut(&keycode) {
-                                match e {
-                                    xlib::KeyPress => {
-                                        for (id, mods, pressed) in entry {
-                                            if event_mods == *mods && !*pressed {
-                                                GlobalHotKeyEvent::send(GlobalHotKeyEvent {
-                                                    id: *id,
-                                                    state: crate::HotKeyState::Pressed,
-                                                });
-                                                *pressed = true;
+                        e @ (xlib::KeyPress
+                        | xlib::KeyRelease
+                        | xlib::ButtonPress
+                        | xlib::ButtonRelease) => {
+                            let (input, state) = match e {
+                                xlib::KeyPress | xlib::KeyRelease => {
+                                    (Input::Key(event.key.keycode), event.key.state)
+                                }
+                                _ => (Input::Button(event.button.button), event.button.state),
+                            };
+                            let is_press = e == xlib::KeyPress || e == xlib::ButtonPress;
+                            let wheel = input.wheel_direction();
+
+                            let pressed_sides = if is_press {
+                                side_keycodes.pressed(&xlib, display)
+                            } else {
+                                ModifierSides::empty()
+                            };
+
+                            if let Some(entry) = hotkeys.get_mut(&input) {
+                                if is_press {
+                                    for registered in entry {
+                                        let id = registered.hotkey.id();
+                                        if registered.pressed {
+                                            // with detectable auto repeat, key repeats are
+                                            // presses without a release in between
+                                            if masks.matches(state, registered.mods) {
+                                                activations.send(GlobalHotKeyEvent::new(
+                                                    id,
+                                                    HotKeyState::Repeat(0),
+                                                ));
+                                            }
+                                        } else if masks.matches(state, registered.mods)
+                                            && registered.hotkey.sides().satisfied_by(pressed_sides)
+                                        {
+                                            if let Some(direction) = wheel {
+                                                activations.send(GlobalHotKeyEvent::new(
+                                                    id,
+                                                    HotKeyState::Scrolled(direction),
+                                                ));
+                                            } else {
+                                                activations.send(GlobalHotKeyEvent::new(
+                                                    id,
+                                                    HotKeyState::Pressed,
+                                                ));
+                                                registered.pressed = true;
                                             }
                                         }
                                     }
-                                    xlib::KeyRelease => {
-                                        for (id, _, pressed) in entry {
-                                            if *pressed {
-                                                GlobalHotKeyEvent::send(GlobalHotKeyEvent {
-                                                    id: *id,
-                                                    state: crate::HotKeyState::Released,
-                                                });
-                                                *pressed = false;
-                                            }
+                                } else {
+                                    for registered in entry {
+                                        if registered.pressed {
+                                            activations.send(GlobalHotKeyEvent::new(
+                                                registered.hotkey.id(),
+                                                HotKeyState::Released,
+                                            ));
+                                            registered.pressed = false;
                                         }
                                     }
-                                    _ => {}
                                 }
                             }
+
+                            let sequence_event = if is_press {
+                                if sequences.pressed.contains_key(&input) {
+                                    None
+                                } else {
+                                    sequences
+                                        .grabbed
+                                        .iter()
+                                        .filter(|g| {
+                                            g.1 == input
+                                                && masks.matches(state, g.2)
+                                                && g.0.sides().satisfied_by(pressed_sides)
+                                        })
+                                        // prefer `RCtrl+K` over `Ctrl+K` when both are waited for
+                                        .max_by_key(|g| g.0.sides().bits().count_ones())
+                                        .map(|g| g.0.id())
+                                        .map(|stroke| {
+                                            sequences.pressed.insert(input, stroke);
+                                            let id =
+                                                sequences.tracker.press(stroke, Instant::now());
+                                            let state = match wheel {
+                                                Some(direction) => HotKeyState::Scrolled(direction),
+                                                None => HotKeyState::Pressed,
+                                            };
+                                            (id, Some(state))
+                                        })
+                                }
+                            } else {
+                                sequences.pressed.remove(&input).map(|stroke| {
+                                    let id = sequences.tracker.release(stroke);
+                                    // a wheel notch is complete once scrolled
+                                    (id, wheel.is_none().then_some(HotKeyState::Released))
+                                })
+                            };
+                            if let Some((id, state)) = sequence_event {
+                                if let (Some(id), Some(state)) = (id, state) {
+                                    activations.send(GlobalHotKeyEvent::new(id, state));
+                                }
+                                sync_sequence_grabs(
+                                    &xlib,
+                                    display,
+                                    root,
+                                    &mut grabs,
+                                    &mut sequences,
+                                );
+                            }
+                        }
+                        xlib::MappingNotify => {
+                            (xlib.XRefreshKeyboardMapping)(&mut event.mapping);
+                            masks = ModifierMasks::new(&xlib, display);
+                        }
+                        xlib::GenericEvent => {
+                            let mut cookie = event.generic_event_cookie;
+                            let opcode = taps.xinput2.as_ref().map(|(_, opcode)| *opcode);
+                            if Some(cookie.extension) == opcode
+                                && (xlib.XGetEventData)(display, &mut cookie) != 0
+                            {
+                                let raw = &*(cookie.data as *const xinput2::XIRawEvent);
+                                for id in taps.handle_raw_event(cookie.evtype, raw.detail as u8) {
+                                    activations
+                                        .send(GlobalHotKeyEvent::new(id, HotKeyState::Pressed));
+                                    activations
+                                        .send(GlobalHotKeyEvent::new(id, HotKeyState::Released));
+                                }
+                                (xlib.XFreeEventData)(display, &mut cookie);
+                            }
                         }
                         _ => {}
                     }
                 }
 
+                let now = Instant::now();
+                if sequences.tracker.deadline().is_some_and(|d| d < now) {
+                    sequences.tracker.expire(now);
+                    sync_sequence_grabs(&xlib, display, root, &mut grabs, &mut sequences);
+                }
+
                 if let Ok(msg) = thread_rx.try_recv() {
                     match msg {
                         ThreadMessage::RegisterHotKey(hotkey, tx) => {
-                            let _ = tx.send(register_hotkey(
+                            let result = register_hotkey(
                                 &xlib,
                                 display,
                                 root,
                                 &mut hotkeys,
+                                &mut grabs,
+                                &mut taps,
+                                &mut parked,
                                 hotkey,
-                            ));
+                            );
+                            sync_registry(&registry, &hotkeys, &taps, &parked);
+                            let _ = tx.send(result);
                         }
                         ThreadMessage::RegisterHotKeys(keys, tx) => {
-                            for hotkey in keys {
-                                if let Err(e) =
-                                    register_hotkey(&xlib, display, root, &mut hotkeys, hotkey)
-                                {
-                                    let _ = tx.send(Err(e));
+                            let mut result = Ok(());
+                            for (i, &hotkey) in keys.iter().enumerate() {
+                                if let Err(e) = register_hotkey(
+                                    &xlib,
+                                    display,
+                                    root,
+                                    &mut hotkeys,
+                                    &mut grabs,
+                                    &mut taps,
+                                    &mut parked,
+                                    hotkey,
+                                ) {
+                                    // all or nothing, release what this batch already grabbed
+                                    for &hotkey in keys[..i].iter().rev() {
+                                        let _ = unregister_hotkey(
+                                            &xlib,
+                                            display,
+                                            root,
+                                            &mut hotkeys,
+                                            &mut grabs,
+                                            &mut taps,
+                                            &mut parked,
+                                            hotkey,
+                                        );
+                                    }
+                                    result = Err(e);
+                                    break;
                                 }
                             }
-                            let _ = tx.send(Ok(()));
+                            sync_registry(&registry, &hotkeys, &taps, &parked);
+                            let _ = tx.send(result);
+                        }
+                        ThreadMessage::RegisterEach(keys, tx) => {
+                            let results = keys
+                                .into_iter()
+                                .map(|hotkey| {
+                                    register_hotkey(
+                                        &xlib,
+                                        display,
+                                        root,
+                                        &mut hotkeys,
+                                        &mut grabs,
+                                        &mut taps,
+                                        &mut parked,
+                                        hotkey,
+                                    )
+                                })
+                                .collect();
+                            sync_registry(&registry, &hotkeys, &taps, &parked);
+                            let _ = tx.send(results);
                         }
                         ThreadMessage::UnRegisterHotKey(hotkey, tx) => {
-                            let _ = tx.send(unregister_hotkey(
+                            let result = unregister_hotkey(
                                 &xlib,
                                 display,
                                 root,
                                 &mut hotkeys,
+                                &mut grabs,
+                                &mut taps,
+                                &mut parked,
                                 hotkey,
-                            ));
+                            );
+                            sync_registry(&registry, &hotkeys, &taps, &parked);
+                            let _ = tx.send(result);
                         }
                         ThreadMessage::UnRegisterHotKeys(keys, tx) => {
+                            // release every hotkey even after a failure, and report the first one
+                            let mut result = Ok(());
                             for hotkey in keys {
-                                if let Err(e) =
-                                    unregister_hotkey(&xlib, display, root, &mut hotkeys, hotkey)
-                                {
-                                    let _ = tx.send(Err(e));
+                                let unregistered = unregister_hotkey(
+                                    &xlib,
+                                    display,
+                                    root,
+                                    &mut hotkeys,
+                                    &mut grabs,
+                                    &mut taps,
+                                    &mut parked,
+                                    hotkey,
+                                );
+                                if result.is_ok() {
+                                    result = unregistered;
                                 }
                             }
-                            let _ = tx.send(Ok(()));
+                            sync_registry(&registry, &hotkeys, &taps, &parked);
+                            let _ = tx.send(result);
+                        }
+                        ThreadMessage::Rebind(old, new, tx) => {
+                            let result = rebind_hotkey(
+                                &xlib,
+                                display,
+                                root,
+                                &mut hotkeys,
+                                &mut grabs,
+                                &mut taps,
+                                &mut parked,
+                                old,
+                                new,
+                            );
+                            sync_registry(&registry, &hotkeys, &taps, &parked);
+                            let _ = tx.send(result);
+                        }
+                        ThreadMessage::RegisterSequence(sequence, tx) => {
+                            let _ = tx.send(register_sequence(
+                                &xlib,
+                                display,
+                                root,
+                                &mut grabs,
+                                &mut sequences,
+                                sequence,
+                            ));
+                        }
+                        ThreadMessage::UnRegisterSequence(sequence, tx) => {
+                            let result = if sequences.tracker.unregister(sequence.id()).is_some() {
+                                sync_sequence_grabs(
+                                    &xlib,
+                                    display,
+                                    root,
+                                    &mut grabs,
+                                    &mut sequences,
+                                );
+                                Ok(())
+                            } else {
+                                Err(crate::Error::FailedToUnRegister {
+                                    hotkey: sequence.strokes()[0],
+                                    reason: FailureReason::NotRegistered,
+                                })
+                            };
+                            let _ = tx.send(result);
+                        }
+                        ThreadMessage::SetEnabled(hotkey, enabled, tx) => {
+                            let result = set_hotkey_enabled(
+                                &xlib,
+                                display,
+                                root,
+                                &mut hotkeys,
+                                &mut grabs,
+                                &mut taps,
+                                &mut parked,
+                                hotkey,
+                                enabled,
+                            );
+                            sync_registry(&registry, &hotkeys, &taps, &parked);
+                            let _ = tx.send(result);
+                        }
+                        ThreadMessage::PauseAll(tx) => {
+                            let result = pause_hotkeys(
+                                &xlib,
+                                display,
+                                root,
+                                &mut hotkeys,
+                                &mut grabs,
+                                &mut taps,
+                                &mut parked,
+                                &mut sequences,
+                            );
+                            sync_registry(&registry, &hotkeys, &taps, &parked);
+                            let _ = tx.send(result);
+                        }
+                        ThreadMessage::ResumeAll(tx) => {
+                            let result = resume_hotkeys(
+                                &xlib,
+                                display,
+                                root,
+                                &mut hotkeys,
+                                &mut grabs,
+                                &mut taps,
+                                &mut parked,
+                                &mut sequences,
+                            );
+                            sync_registry(&registry, &hotkeys, &taps, &parked);
+                            let _ = tx.send(result);
                         }
                         ThreadMessage::DropThread => {
                             (xlib.XCloseDisplay)(display);
@@ -299,122 +1353,287 @@ fn events_processor(thread_rx: Receiver<ThreadMessage>) {
     }
 }
 
-fn keycode_to_x11_scancode(key: Code) -> Option<u32> {
+/// Returns the evdev scancode of the key at this position, X11 keycodes are the evdev scancodes offset by 8.
+fn code_to_evdev(key: Code) -> Option<u32> {
     Some(match key {
-        Code::KeyA => keysym::XK_A,
-        Code::KeyB => keysym::XK_B,
-        Code::KeyC => keysym::XK_C,
-        Code::KeyD => keysym::XK_D,
-        Code::KeyE => keysym::XK_E,
-        Code::KeyF => keysym::XK_F,
-        Code::KeyG => keysym::XK_G,
-        Code::KeyH => keysym::XK_H,
-        Code::KeyI => keysym::XK_I,
-        Code::KeyJ => keysym::XK_J,
-        Code::KeyK => keysym::XK_K,
-        Code::KeyL => keysym::XK_L,
-        Code::KeyM => keysym::XK_M,
-        Code::KeyN => keysym::XK_N,
-        Code::KeyO => keysym::XK_O,
-        Code::KeyP => keysym::XK_P,
-        Code::KeyQ => keysym::XK_Q,
-        Code::KeyR => keysym::XK_R,
-        Code::KeyS => keysym::XK_S,
-        Code::KeyT => keysym::XK_T,
-        Code::KeyU => keysym::XK_U,
-        Code::KeyV => keysym::XK_V,
-        Code::KeyW => keysym::XK_W,
-        Code::KeyX => keysym::XK_X,
-        Code::KeyY => keysym::XK_Y,
-        Code::KeyZ => keysym::XK_Z,
-        Code::Backslash => keysym::XK_backslash,
-        Code::BracketLeft => keysym::XK_bracketleft,
-        Code::BracketRight => keysym::XK_bracketright,
-        Code::Backquote => keysym::XK_quoteleft,
-        Code::Comma => keysym::XK_comma,
-        Code::Digit0 => keysym::XK_0,
-        Code::Digit1 => keysym::XK_1,
-        Code::Digit2 => keysym::XK_2,
-        Code::Digit3 => keysym::XK_3,
-        Code::Digit4 => keysym::XK_4,
-        Code::Digit5 => keysym::XK_5,
-        Code::Digit6 => keysym::XK_6,
-        Code::Digit7 => keysym::XK_7,
-        Code::Digit8 => keysym::XK_8,
-        Code::Digit9 => keysym::XK_9,
-        Code::Equal => keysym::XK_equal,
-        Code::Minus => keysym::XK_minus,
-        Code::Period => keysym::XK_period,
-        Code::Quote => keysym::XK_leftsinglequotemark,
-        Code::Semicolon => keysym::XK_semicolon,
-        Code::Slash => keysym::XK_slash,
-        Code::Backspace => keysym::XK_BackSpace,
-        Code::CapsLock => keysym::XK_Caps_Lock,
-        Code::Enter => keysym::XK_Return,
-        Code::Space => keysym::XK_space,
-        Code::Tab => keysym::XK_Tab,
-        Code::Delete => keysym::XK_Delete,
-        Code::End => keysym::XK_End,
-        Code::Home => keysym::XK_Home,
-        Code::Insert => keysym::XK_Insert,
-        Code::PageDown => keysym::XK_Page_Down,
-        Code::PageUp => keysym::XK_Page_Up,
-        Code::ArrowDown => keysym::XK_Down,
-        Code::ArrowLeft => keysym::XK_Left,
-        Code::ArrowRight => keysym::XK_Right,
-        Code::ArrowUp => keysym::XK_Up,
-        Code::Numpad0 => keysym::XK_KP_0,
-        Code::Numpad1 => keysym::XK_KP_1,
-        Code::Numpad2 => keysym::XK_KP_2,
-        Code::Numpad3 => keysym::XK_KP_3,
-        Code::Numpad4 => keysym::XK_KP_4,
-        Code::Numpad5 => keysym::XK_KP_5,
-        Code::Numpad6 => keysym::XK_KP_6,
-        Code::Numpad7 => keysym::XK_KP_7,
-        Code::Numpad8 => keysym::XK_KP_8,
-        Code::Numpad9 => keysym::XK_KP_9,
-        Code::NumpadAdd => keysym::XK_KP_Add,
-        Code::NumpadDecimal => keysym::XK_KP_Decimal,
-        Code::NumpadDivide => keysym::XK_KP_Divide,
-        Code::NumpadMultiply => keysym::XK_KP_Multiply,
-        Code::NumpadSubtract => keysym::XK_KP_Subtract,
-        Code::Escape => keysym::XK_Escape,
-        Code::PrintScreen => keysym::XK_Print,
-        Code::ScrollLock => keysym::XK_Scroll_Lock,
-        Code::AudioVolumeDown => keysym::XF86XK_AudioLowerVolume,
-        Code::AudioVolumeMute => keysym::XF86XK_AudioMute,
-        Code::AudioVolumeUp => keysym::XF86XK_AudioRaiseVolume,
-        Code::NumLock => keysym::XK_F1,
-        Code::F1 => keysym::XK_F1,
-        Code::F2 => keysym::XK_F2,
-        Code::F3 => keysym::XK_F3,
-        Code::F4 => keysym::XK_F4,
-        Code::F5 => keysym::XK_F5,
-        Code::F6 => keysym::XK_F6,
-        Code::F7 => keysym::XK_F7,
-        Code::F8 => keysym::XK_F8,
-        Code::F9 => keysym::XK_F9,
-        Code::F10 => keysym::XK_F10,
-        Code::F11 => keysym::XK_F11,
-        Code::F12 => keysym::XK_F12,
-
+        Code::KeyA => 30,
+        Code::KeyB => 48,
+        Code::KeyC => 46,
+        Code::KeyD => 32,
+        Code::KeyE => 18,
+        Code::KeyF => 33,
+        Code::KeyG => 34,
+        Code::KeyH => 35,
+        Code::KeyI => 23,
+        Code::KeyJ => 36,
+        Code::KeyK => 37,
+        Code::KeyL => 38,
+        Code::KeyM => 50,
+        Code::KeyN => 49,
+        Code::KeyO => 24,
+        Code::KeyP => 25,
+        Code::KeyQ => 16,
+        Code::KeyR => 19,
+        Code::KeyS => 31,
+        Code::KeyT => 20,
+        Code::KeyU => 22,
+        Code::KeyV => 47,
+        Code::KeyW => 17,
+        Code::KeyX => 45,
+        Code::KeyY => 21,
+        Code::KeyZ => 44,
+        Code::Backslash => 43,
+        Code::BracketLeft => 26,
+        Code::BracketRight => 27,
+        Code::Backquote => 41,
+        Code::Comma => 51,
+        Code::Digit0 => 11,
+        Code::Digit1 => 2,
+        Code::Digit2 => 3,
+        Code::Digit3 => 4,
+        Code::Digit4 => 5,
+        Code::Digit5 => 6,
+        Code::Digit6 => 7,
+        Code::Digit7 => 8,
+        Code::Digit8 => 9,
+        Code::Digit9 => 10,
+        Code::Equal => 13,
+        Code::Minus => 12,
+        Code::Period => 52,
+        Code::Quote => 40,
+        Code::Semicolon => 39,
+        Code::Slash => 53,
+        Code::Backspace => 14,
+        Code::CapsLock => 58,
+        Code::Enter => 28,
+        Code::Space => 57,
+        Code::Tab => 15,
+        Code::Delete => 111,
+        Code::End => 107,
+        Code::Home => 102,
+        Code::Insert => 110,
+        Code::PageDown => 109,
+        Code::PageUp => 104,
+        Code::ArrowDown => 108,
+        Code::ArrowLeft => 105,
+        Code::ArrowRight => 106,
+        Code::ArrowUp => 103,
+        Code::Numpad0 => 82,
+        Code::Numpad1 => 79,
+        Code::Numpad2 => 80,
+        Code::Numpad3 => 81,
+        Code::Numpad4 => 75,
+        Code::Numpad5 => 76,
+        Code::Numpad6 => 77,
+        Code::Numpad7 => 71,
+        Code::Numpad8 => 72,
+        Code::Numpad9 => 73,
+        Code::NumpadAdd => 78,
+        Code::NumpadDecimal => 83,
+        Code::NumpadDivide => 98,
+        Code::NumpadMultiply => 55,
+        Code::NumpadSubtract => 74,
+        Code::Escape => 1,
+        Code::PrintScreen => 99,
+        Code::ScrollLock => 70,
+        Code::AudioVolumeDown => 114,
+        Code::AudioVolumeMute => 113,
+        Code::AudioVolumeUp => 115,
+        Code::MediaTrackNext => 163,
+        Code::MediaPlayPause => 164,
+        Code::MediaTrackPrevious => 165,
+        Code::MediaStop => 166,
+        Code::NumLock => 69,
+        Code::F1 => 59,
+        Code::F2 => 60,
+        Code::F3 => 61,
+        Code::F4 => 62,
+        Code::F5 => 63,
+        Code::F6 => 64,
+        Code::F7 => 65,
+        Code::F8 => 66,
+        Code::F9 => 67,
+        Code::F10 => 68,
+        Code::F11 => 87,
+        Code::F12 => 88,
+        Code::IntlBackslash => 86,
+        Code::IntlRo => 89,
+        Code::IntlYen => 124,
+        Code::AltLeft => 56,
+        Code::AltRight => 100,
+        Code::ContextMenu => 127,
+        Code::ControlLeft => 29,
+        Code::ControlRight => 97,
+        Code::MetaLeft => 125,
+        Code::MetaRight => 126,
+        Code::ShiftLeft => 42,
+        Code::ShiftRight => 54,
+        Code::Convert => 92,
+        Code::KanaMode => 93,
+        Code::Lang1 => 122,
+        Code::Lang2 => 123,
+        Code::Lang3 => 90,
+        Code::Lang4 => 91,
+        Code::Lang5 => 85,
+        Code::NonConvert => 94,
+        Code::Help => 138,
+        Code::NumpadComma => 121,
+        Code::NumpadEnter => 96,
+        Code::NumpadEqual => 117,
+        Code::NumpadParenLeft => 179,
+        Code::NumpadParenRight => 180,
+        Code::Pause => 119,
+        Code::BrowserBack => 158,
+        Code::BrowserFavorites => 156,
+        Code::BrowserForward => 159,
+        Code::BrowserHome => 172,
+        Code::BrowserRefresh => 173,
+        Code::BrowserSearch => 217,
+        Code::BrowserStop => 128,
+        Code::Eject => 161,
+        Code::LaunchApp1 => 144,
+        Code::LaunchApp2 => 140,
+        Code::LaunchMail => 155,
+        Code::MediaSelect => 171,
+        Code::Power => 116,
+        Code::Sleep => 142,
+        Code::WakeUp => 143,
+        Code::Suspend => 205,
+        Code::Again => 129,
+        Code::Copy => 133,
+        Code::Cut => 137,
+        Code::Find => 136,
+        Code::Open => 134,
+        Code::Paste => 135,
+        Code::Props => 130,
+        Code::Undo => 131,
+        Code::Hiragana => 91,
+        Code::Katakana => 90,
+        Code::F13 => 183,
+        Code::F14 => 184,
+        Code::F15 => 185,
+        Code::F16 => 186,
+        Code::F17 => 187,
+        Code::F18 => 188,
+        Code::F19 => 189,
+        Code::F20 => 190,
+        Code::F21 => 191,
+        Code::F22 => 192,
+        Code::F23 => 193,
+        Code::F24 => 194,
+        Code::BrightnessDown => 224,
+        Code::BrightnessUp => 225,
+        Code::DisplayToggleIntExt => 227,
+        Code::MailForward => 233,
+        Code::MailReply => 232,
+        Code::MailSend => 231,
+        Code::MediaFastForward => 208,
+        Code::MediaPause => 201,
+        Code::MediaPlay => 200,
+        Code::MediaRecord => 167,
+        Code::MediaRewind => 168,
+        Code::ShowAllWindows => 120,
+        // the other keys, like `Fn` or `F25`, have no evdev scancode an X11 keycode can hold
         _ => return None,
     })
 }
 
-fn modifiers_to_x11_mods(modifiers: Modifiers) -> u32 {
-    let mut x11mods = 0;
-    if modifiers.contains(Modifiers::SHIFT) {
-        x11mods |= xlib::ShiftMask;
+/// Returns the keysym of a character, see Appendix A of the X11 protocol for the encoding.
+fn char_to_keysym(c: char) -> u32 {
+    match c as u32 {
+        // Latin-1 characters are their own keysym
+        cp @ (0x20..=0x7e | 0xa0..=0xff) => cp,
+        cp => 0x0100_0000 + cp,
     }
-    if modifiers.intersects(Modifiers::SUPER | Modifiers::META) {
-        x11mods |= xlib::Mod4Mask;
+}
+
+/// The `ModN` masks of the modifiers that aren't fixed by X11,
+/// which depend on the keymap and are looked up at runtime.
+#[derive(Debug, Clone, Copy)]
+struct ModifierMasks {
+    alt: u32,
+    super_: u32,
+    meta: u32,
+    hyper: u32,
+    alt_graph: u32,
+}
+
+impl ModifierMasks {
+    fn new(xlib: &Xlib, display: *mut _XDisplay) -> Self {
+        let mask = |keysyms: &[u32]| {
+            keysyms.iter().fold(0, |mask, &keysym| {
+                mask | unsafe { (xlib.XkbKeysymToModifiers)(display, keysym as _) }
+            })
+        };
+        let or_default = |mask, default| if mask == 0 { default } else { mask };
+        Self {
+            alt: or_default(mask(&[keysym::XK_Alt_L, keysym::XK_Alt_R]), xlib::Mod1Mask),
+            super_: or_default(
+                mask(&[keysym::XK_Super_L, keysym::XK_Super_R]),
+                xlib::Mod4Mask,
+            ),
+            meta: mask(&[keysym::XK_Meta_L, keysym::XK_Meta_R]),
+            hyper: mask(&[keysym::XK_Hyper_L, keysym::XK_Hyper_R]),
+            alt_graph: mask(&[keysym::XK_ISO_Level3_Shift, keysym::XK_Mode_switch]),
+        }
     }
-    if modifiers.contains(Modifiers::ALT) {
-        x11mods |= xlib::Mod1Mask;
+
+    /// Returns the X11 modifiers mask of `modifiers`,
+    /// or `None` if one of them isn't mapped to a `ModN` mask in the current keymap.
+    fn to_x11(self, modifiers: Modifiers) -> Option<u32> {
+        [
+            (Modifiers::SHIFT, xlib::ShiftMask),
+            (Modifiers::CONTROL, xlib::ControlMask),
+            (Modifiers::CAPS_LOCK, xlib::LockMask),
+            (Modifiers::ALT, self.alt),
+            (Modifiers::SUPER, self.super_),
+            (Modifiers::META, self.meta),
+            (Modifiers::HYPER, self.hyper),
+            (Modifiers::ALT_GRAPH, self.alt_graph),
+        ]
+        .into_iter()
+        .filter(|(modifier, _)| modifiers.contains(*modifier))
+        .try_fold(0, |x11mods, (_, mask)| {
+            (mask != 0).then_some(x11mods | mask)
+        })
     }
-    if modifiers.contains(Modifiers::CONTROL) {
-        x11mods |= xlib::ControlMask;
+
+    /// Returns `true` if the `state` of an event has exactly the modifiers in `mods`.
+    ///
+    /// X11 sends masks for the lock keys also, CapsLock is only compared when `mods` requires it
+    /// and NumLock never is.
+    fn matches(self, state: u32, mods: u32) -> bool {
+        let compared = xlib::ShiftMask
+            | xlib::ControlMask
+            | self.alt
+            | self.super_
+            | self.meta
+            | self.hyper
+            | self.alt_graph
+            | (mods & xlib::LockMask);
+        state & compared & !xlib::Mod2Mask == mods
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use keyboard_types::{Code, Modifiers};
+
+    use super::GlobalHotKeyManager;
+    use crate::hotkey::HotKey;
+
+    #[test]
+    fn hotkeys_are_read_without_the_events_thread() {
+        let hotkey = HotKey::new(Some(Modifiers::CONTROL), Code::KeyA);
+        // an events thread that never answers, like one busy calling the event handler
+        let (thread_tx, _thread_rx) = crossbeam_channel::unbounded();
+        let manager = GlobalHotKeyManager {
+            thread_tx,
+            registry: Arc::new(Mutex::new(vec![hotkey])),
+        };
+
+        assert_eq!(manager.hotkeys(), vec![hotkey]);
     }
-    x11mods
 }